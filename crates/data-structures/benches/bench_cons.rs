@@ -0,0 +1,51 @@
+#![feature(test)]
+extern crate test;
+
+use minilisp_data_structures::{car, cdr, pair, Cell, Value};
+use test::{black_box, Bencher};
+
+const LENGTH: u32 = 2_000;
+
+/// Builds a list of `LENGTH` integers from its last element to its
+/// first, one `cons` at a time.
+fn consed() -> Value<'static> {
+    (0..LENGTH).rev().fold(Value::Nil, |list, n| pair(Value::unsigned_integer(n), list))
+}
+
+#[bench]
+fn bench_cons(bencher: &mut Bencher) {
+    bencher.iter(|| black_box(consed()));
+}
+
+#[bench]
+fn bench_collect(bencher: &mut Bencher) {
+    bencher.iter(|| black_box((0..LENGTH).map(Value::unsigned_integer).collect::<Value>()));
+}
+
+/// Appending walks the whole list on every element, for comparison
+/// with [`bench_cons`].
+#[bench]
+fn bench_push_value(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        let mut cell = Cell::nil();
+        for n in 0..LENGTH {
+            cell.push_value(Value::unsigned_integer(n));
+        }
+        black_box(cell)
+    });
+}
+
+#[bench]
+fn bench_car_cdr_traversal(bencher: &mut Bencher) {
+    let list = consed();
+    bencher.iter(|| {
+        let mut rest = list.clone();
+        let mut count = 0;
+        while !rest.is_empty() && !rest.is_nil() {
+            black_box(car(&rest));
+            rest = cdr(&rest);
+            count += 1;
+        }
+        assert_eq!(count, LENGTH);
+    });
+}
@@ -1,13 +1,13 @@
 #![allow(unused)]
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
 use std::iter::{Extend, IntoIterator, Iterator};
-use std::ops::Deref;
+use std::rc::{Rc, Weak};
 
-use unique_pointer::{RefCounter, UniquePointer};
-
-use crate::{AsSymbol, AsValue, Quotable, Symbol, Value};
+use crate::{circular, printer, AsSymbol, AsValue, Quotable, Symbol, Value};
 pub trait ListIterator<'c, T: AsCell<'c>>: IntoIterator<Item = T> + Debug + Quotable {
     fn iter_cells(&self) -> Cell<'c>;
 }
@@ -17,11 +17,27 @@ pub trait AsCell<'c>: Quotable {
     fn as_cell(&self) -> Cell<'c>;
 }
 
-#[derive(Eq, PartialOrd, Ord)]
+/// One cons cell of a list.
+struct Pair<'c> {
+    car: Value<'c>,
+    /// `Value::List` of the next cell, `Value::Nil` at the end of a
+    /// proper list or any other value at the end of an improper one,
+    /// as in `(a . b)`
+    cdr: Value<'c>,
+}
+
+type PairRef<'c> = Rc<RefCell<Pair<'c>>>;
+
+/// Handle to the first cons cell of a list, or to no cell at all for
+/// the empty list.
+///
+/// Cons cells are shared rather than copied: clones of a `Cell`, and
+/// the lists spliced onto it with [`Cell::set_cdr`], point to the same
+/// cells, so a mutation through one of them is visible through every
+/// other, as in elisp.
+#[derive(Clone, Default)]
 pub struct Cell<'c> {
-    pub(crate) head: UniquePointer<Value<'c>>,
-    pub(crate) tail: UniquePointer<Cell<'c>>,
-    pub(crate) refs: RefCounter,
+    pair: Option<PairRef<'c>>,
     pub(crate) quoted: bool,
 }
 
@@ -31,33 +47,118 @@ impl<'c> Cell<'c> {
     }
 
     pub fn quoted<T: AsValue<'c>>(item: Option<T>, quoted: bool) -> Cell<'c> {
-        let mut cell = Cell {
-            head: UniquePointer::<Value<'c>>::null(),
-            tail: UniquePointer::<Cell<'c>>::null(),
-            refs: RefCounter::new(),
-            quoted,
-        };
-        cell.incr_ref();
-        if let Some(item) = item {
-            cell.write(item.as_value());
+        match item {
+            Some(item) => {
+                let mut cell = Cell::link(item.as_value(), Value::Nil);
+                cell.quoted = quoted;
+                cell
+            },
+            None => Cell {
+                pair: None,
+                quoted,
+            },
         }
+    }
+
+    /// The cons cell `(car . cdr)`, sharing `cdr` when it is a list
+    /// rather than copying it.
+    ///
+    /// `O(1)`
+    pub fn cons(car: Value<'c>, cdr: Value<'c>) -> Cell<'c> {
+        let cell = Cell::link(car, Value::Nil);
+        cell.set_cdr(cdr);
         cell
     }
 
+    /// A new cons cell of `car` and `cdr`, which must be `nil`, a
+    /// `Value::List` or an atom.
+    fn link(car: Value<'c>, cdr: Value<'c>) -> Cell<'c> {
+        Cell {
+            pair: Some(Rc::new(RefCell::new(Pair { car, cdr }))),
+            quoted: false,
+        }
+    }
+
     pub fn is_nil(&self) -> bool {
-        self.head.is_null() && self.tail.is_null()
+        self.pair.is_none()
+    }
+
+    /// `true` when `self` and `other` are the same cons cell, or both
+    /// the empty list.
+    pub fn ptr_eq(&self, other: &Cell<'c>) -> bool {
+        match (&self.pair, &other.pair) {
+            (Some(pair), Some(other)) => Rc::ptr_eq(pair, other),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Address of the first cell, identifying it while it is alive,
+    /// `None` for the empty list.
+    pub fn as_ptr(&self) -> Option<*const ()> {
+        self.pair.as_ref().map(|pair| Rc::as_ptr(pair).cast())
+    }
+
+    /// Handle to the first cell that does not keep it alive, `None`
+    /// for the empty list.
+    pub fn downgrade(&self) -> Option<WeakCell<'c>> {
+        self.pair.as_ref().map(|pair| WeakCell {
+            pair: Rc::downgrade(pair),
+        })
+    }
+
+    /// `true` when this list ends in `nil`, rather than in a non-list
+    /// value, as in `(a . b)`, or not at all, as a circular list.
+    ///
+    /// `O(n)`
+    pub fn is_proper(&self) -> bool {
+        let (_, circular) = self.extent();
+        !circular && self.last().cdr().is_nil()
+    }
+
+    /// `true` when following the cdrs of this list leads back to one
+    /// of its cells.
+    ///
+    /// `O(n)`
+    pub fn is_circular(&self) -> bool {
+        self.extent().1
+    }
+
+    /// Returns the non-list cdr of the last cell of an improper list.
+    ///
+    /// `O(n)`
+    pub fn dotted_tail(&self) -> Option<Value<'c>> {
+        match self.last().cdr() {
+            Value::Nil | Value::List(_) => None,
+            value => Some(value),
+        }
+    }
+
+    /// Sets the cdr of the last cell of this list to `value`.
+    ///
+    /// A list is shared rather than copied, so that `(a . (b c))` is
+    /// `(a b c)`, `nil` ends the list and any other value makes it
+    /// improper.
+    pub fn set_dotted_tail(&mut self, value: Value<'c>) {
+        if self.is_nil() {
+            if let Value::List(cell) | Value::QuotedList(cell) = &value {
+                self.pair = cell.pair.clone();
+            }
+            return;
+        }
+        self.last().set_cdr(value);
     }
 
     pub fn unwrap_value(&self) -> Value<'c> {
-        if self.tail.is_null() {
+        if self.cdr().is_nil() {
             match self.head() {
                 Some(head) => head.unwrap_list(),
                 None => Value::Nil,
             }
         } else if self.quoted {
-            Value::QuotedList(self.as_cell())
+            Value::QuotedList(self.clone())
         } else {
-            Value::List(self.as_cell())
+            Value::List(self.clone())
         }
     }
 
@@ -65,205 +166,267 @@ impl<'c> Cell<'c> {
         let value = item.as_value();
         let is_quoted = value.is_quoted();
         Cell::quoted(Some(value), is_quoted)
-        // let mut cell = Cell::nil();
-        // cell.write(item.as_value());
-        // cell
     }
 
+    /// The car of the first cell, `None` for the empty list.
+    ///
+    /// `O(1)`
     pub fn head(&self) -> Option<Value<'c>> {
-        self.head.try_read()
+        self.pair.as_ref().map(|pair| pair.borrow().car.clone())
+    }
+
+    /// The cdr of the first cell: `Value::List` of the rest of the
+    /// list, `nil` or the dotted tail of an improper list.
+    ///
+    /// `O(1)`
+    pub fn cdr(&self) -> Value<'c> {
+        match &self.pair {
+            Some(pair) => pair.borrow().cdr.clone(),
+            None => Value::Nil,
+        }
+    }
+
+    /// Replaces the car of the first cell, returning `false` for the
+    /// empty list, which has none.
+    ///
+    /// `O(1)`
+    pub fn set_car(&self, value: Value<'c>) -> bool {
+        match &self.pair {
+            Some(pair) => {
+                pair.borrow_mut().car = value;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Replaces the cdr of the first cell, returning `false` for the
+    /// empty list, which has none.
+    ///
+    /// A list is shared rather than copied and the empty list is
+    /// stored as `nil`.
+    ///
+    /// `O(1)`
+    pub fn set_cdr(&self, value: Value<'c>) -> bool {
+        match &self.pair {
+            Some(pair) => {
+                pair.borrow_mut().cdr = match &value {
+                    Value::List(cell) | Value::QuotedList(cell) if !cell.is_nil() =>
+                        Value::List(cell.unquote()),
+                    Value::List(_)
+                    | Value::QuotedList(_)
+                    | Value::EmptyList
+                    | Value::EmptyQuotedList => Value::Nil,
+                    _ => value,
+                };
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Handle to the last cell of this list, or of a circular list the
+    /// cell whose cdr leads back.
+    ///
+    /// `O(n)`
+    pub fn last(&self) -> Cell<'c> {
+        self.cells().last().unwrap_or_else(|| self.unquote())
     }
 
+    /// Appends `value` after the last cell of this list.
+    ///
+    /// `O(n)`: build long lists with [`Cell::cons`] or by collecting
+    /// an iterator instead.
     pub fn push_value(&mut self, value: Value<'c>) {
-        let is_quoted = value.is_quoted();
-        let mut cell = Cell::quoted(Some(value), is_quoted);
-        let cell = if is_quoted {
-            cell.quote()
-        } else {
-            cell
-        };
-        self.add(&cell);
+        self.append(Cell::link(value, Value::Nil));
     }
 
+    /// Appends a copy of the cells of `new`.
     pub fn add(&mut self, new: &Cell<'c>) {
         if new.is_nil() {
             return;
         }
-        let mut new = new.clone();
-        self.incr_ref();
-
-        if self.head.is_null() {
-            // when self.head *IS* null:
-            // and `new.head` *IS NOT* null
-            if !new.head.is_null() {
-                // swap heads
-                self.swap_head(&mut new);
-            }
-
-            // and new.tail *IS NOT* null
-            if new.tail.is_not_null() {
-                let tail = new.tail.inner_mut();
-                // if new.tail.head *IS NOT* null
-                if new.head.is_not_null() {
-                    // write new.tail.head in tail.head
-                    tail.head.write_ref(new.head.inner_ref());
+        let mut copy = Cell::from_iter(new.values());
+        if let Some(dotted_tail) = new.dotted_tail() {
+            copy.set_dotted_tail(dotted_tail);
+        }
+        self.append(copy);
+    }
 
-                    // TODO:
-                    // try new.swap_head(tail);
-                }
-            }
-            self.tail = UniquePointer::from(new);
+    /// Links `cell` after the last cell of this list, in place of its
+    /// dotted tail if any.
+    fn append(&mut self, cell: Cell<'c>) {
+        if self.is_nil() {
+            self.pair = cell.pair.clone();
         } else {
-            // when self.head *IS NOT* null
-            if self.tail.is_null() {
-                // when self.tail *IS* null
-                self.tail = UniquePointer::from(new);
-            } else {
-                //  self.tail *IS NOT* null
-                self.tail.inner_mut().add(&new);
-            }
+            self.last().set_cdr(Value::List(cell));
         }
     }
 
+    /// Removes the dotted tail of this list if any, otherwise its last
+    /// element, returning `false` for the empty list.
     pub fn pop(&mut self) -> bool {
-        if !self.tail.is_null() {
-            self.tail.drop_in_place();
-            self.tail = UniquePointer::null();
-            true
-        } else if !self.head.is_null() {
-            self.head.drop_in_place();
-            self.head = UniquePointer::null();
-            true
-        } else {
-            false
+        if self.is_nil() {
+            return false;
+        }
+        if self.dotted_tail().is_some() {
+            self.last().set_cdr(Value::Nil);
+            return true;
         }
+        match self.len() {
+            1 => self.pair = None,
+            len => {
+                if let Some(previous) = self.cells().nth(len - 2) {
+                    previous.set_cdr(Value::Nil);
+                }
+            },
+        }
+        true
     }
 
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
+    /// Number of cells of this list, each cell of a circular list
+    /// counted once.
+    ///
     /// `O(n)`
     pub fn len(&self) -> usize {
-        let mut len = 0;
-        if !self.head.is_null() {
-            len += 1
+        self.extent().0
+    }
+
+    /// Handle to the rest of this list after its first cell, `None`
+    /// at the end of the list.
+    ///
+    /// `O(1)`
+    pub fn tail(&self) -> Option<Cell<'c>> {
+        match &self.pair {
+            Some(pair) => match &pair.borrow().cdr {
+                Value::List(cell) => Some(cell.clone()),
+                _ => None,
+            },
+            None => None,
         }
-        if let Some(tail) = self.tail() {
-            len += tail.len();
-        }
-        len
-    }
-
-    pub fn tail(&self) -> Option<&'c Cell<'c>> {
-        self.tail.as_ref()
     }
 
     pub fn values(&self) -> Vec<Value<'c>> {
-        let mut values = Vec::<Value>::new();
-        if let Some(head) = self.head() {
-            // dbg!(&head);
-            values.push(head.clone());
-        }
-        if let Some(tail) = self.tail() {
-            // dbg!(&tail);
-            values.extend(tail.values());
-        }
-        values
-    }
-
-    pub(crate) fn write(&mut self, value: Value<'c>) {
-        self.head.write(value);
-        self.incr_ref();
+        self.cells().filter_map(|cell| cell.head()).collect()
     }
 
-    pub(crate) fn swap_head(&mut self, other: &mut Self) {
-        // self.incr_ref();
-        // other.incr_ref();
-        self.head = unsafe {
-            let head = other.head.propagate();
-            other.head = self.head.propagate();
-            head
-        };
+    pub fn to_vec(&self) -> Vec<Value<'c>> {
+        self.values()
     }
 
-    pub(crate) fn swap_tail(&mut self, other: &mut Self) {
-        self.tail = unsafe {
-            let tail = other.tail.propagate();
-            other.tail = self.tail.propagate();
-            tail
-        };
+    /// Handles to every cell of this list, in order, stopping before a
+    /// circular list loops back.
+    fn cells(&self) -> impl Iterator<Item = Cell<'c>> {
+        std::iter::successors(Some(self.unquote()).filter(|cell| !cell.is_nil()), Cell::tail)
+            .take(self.extent().0)
     }
 
-    pub(crate) fn swap_refs(&mut self, other: &mut Self) {
-        self.refs = {
-            let refs = other.refs.clone();
-            other.refs = self.refs.clone();
-            refs
-        };
+    /// Number of distinct cells of this list and whether it is
+    /// circular, found with Floyd's tortoise and hare so that no
+    /// cell has to be remembered.
+    ///
+    /// `O(n)`
+    fn extent(&self) -> (usize, bool) {
+        let first = self.unquote();
+        if first.is_nil() {
+            return (0, false);
+        }
+        let (mut tortoise, mut hare) = (first.clone(), first.clone());
+        let mut length = 1;
+        loop {
+            for _ in 0..2 {
+                match hare.tail() {
+                    Some(tail) => hare = tail,
+                    None => return (length, false),
+                }
+                length += 1;
+            }
+            tortoise = tortoise.tail().expect("behind the hare");
+            if tortoise.ptr_eq(&hare) {
+                break;
+            }
+        }
+        // as far from where they met as the start of the list is from
+        // the first cell of the cycle
+        let (mut start, mut lead) = (first, tortoise);
+        let mut before = 0;
+        while !start.ptr_eq(&lead) {
+            start = start.tail().expect("cycle");
+            lead = lead.tail().expect("cycle");
+            before += 1;
+        }
+        let mut cycle = 1;
+        let mut cell = start.tail().expect("cycle");
+        while !cell.ptr_eq(&start) {
+            cell = cell.tail().expect("cycle");
+            cycle += 1;
+        }
+        (before + cycle, true)
+    }
+
+    /// The list of the elements of `cells` in order, ending in the
+    /// dotted tail of the last of them, consed in one pass rather than
+    /// appended one cell at a time.
+    fn concat<I: IntoIterator<Item = Cell<'c>>>(cells: I) -> Cell<'c> {
+        let mut values = Vec::new();
+        let mut dotted_tail = None;
+        for cell in cells.into_iter().filter(|cell| !cell.is_nil()) {
+            values.extend(cell.values());
+            dotted_tail = cell.dotted_tail();
+        }
+        let mut cell = Cell::from_iter(values);
+        if let Some(dotted_tail) = dotted_tail {
+            cell.set_dotted_tail(dotted_tail);
+        }
+        cell
     }
+}
+/// Handle to a cons cell made with [`Cell::downgrade`], which tells
+/// whether the cell is still alive without keeping it alive.
+///
+/// The address of the cell is not reused while a `WeakCell` to it
+/// exists, so it keeps identifying the cell.
+#[derive(Clone)]
+pub struct WeakCell<'c> {
+    pair: Weak<RefCell<Pair<'c>>>,
+}
 
-    pub fn to_vec(&self) -> Vec<Value<'c>> {
-        Vec::<Value<'c>>::from_iter(self.clone().into_iter())
+impl<'c> WeakCell<'c> {
+    /// The cell, unless every [`Cell`] to it was dropped.
+    pub fn upgrade(&self) -> Option<Cell<'c>> {
+        self.pair.upgrade().map(|pair| Cell {
+            pair: Some(pair),
+            quoted: false,
+        })
     }
 
-    fn incr_ref(&mut self) {
-        self.refs.incr();
-        if !self.tail.is_null() {
-            if let Some(tail) = self.tail.as_mut() {
-                tail.incr_ref();
-            }
-        }
+    pub fn is_alive(&self) -> bool {
+        self.pair.strong_count() > 0
     }
 
-    fn decr_ref(&mut self) {
-        self.refs.decr();
-        if !self.tail.is_null() {
-            if let Some(tail) = self.tail.as_mut() {
-                tail.decr_ref();
-            }
-        }
+    /// Address of the cell, the same as [`Cell::as_ptr`].
+    pub fn as_ptr(&self) -> *const () {
+        self.pair.as_ptr().cast()
     }
+}
 
-    fn dealloc(&mut self) {
-        if self.refs > 0 {
-            self.decr_ref();
-        } else {
-            self.head.drop_in_place();
-            self.tail.drop_in_place();
-        }
+impl<'c> PartialEq for WeakCell<'c> {
+    fn eq(&self, other: &WeakCell<'c>) -> bool {
+        self.pair.ptr_eq(&other.pair)
     }
+}
+impl<'c> Eq for WeakCell<'c> {}
 
-    fn repr(&self) -> String {
-        [
-            "Cell".to_string(),
-            format!(
-                "[{}]",
-                if self.is_nil() {
-                    format!("null")
-                } else {
-                    [
-                        if self.head.is_null() {
-                            format!("head: {}", "null")
-                        } else {
-                            format!("head={:#?}", self.head().unwrap_or_default())
-                        },
-                        if self.tail.is_null() {
-                            format!("tail: {}", "null")
-                        } else {
-                            format!(
-                                "tail={:#?}",
-                                self.tail().map(Clone::clone).unwrap_or_default()
-                            )
-                        },
-                    ]
-                    .join(" | ")
-                }
-            ),
-        ]
-        .join("")
+impl Debug for WeakCell<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "WeakCell({:p})", self.as_ptr())
     }
 }
+
 impl<'c> Quotable for Cell<'c> {
     fn is_quoted(&self) -> bool {
         self.quoted
@@ -272,15 +435,23 @@ impl<'c> Quotable for Cell<'c> {
     fn set_quoted(&mut self, quoted: bool) {
         self.quoted = quoted;
     }
+
+    fn quote(&self) -> Cell<'c> {
+        let mut cell = self.clone();
+        cell.quoted = true;
+        cell
+    }
+
+    fn unquote(&self) -> Cell<'c> {
+        let mut cell = self.clone();
+        cell.quoted = false;
+        cell
+    }
 }
 
 impl<'c, T: Quotable + AsCell<'c>, const N: usize> AsCell<'c> for [T; N] {
     fn as_cell(&self) -> Cell<'c> {
-        let mut cell = Cell::nil();
-        for item in self {
-            cell.add(&item.as_cell());
-        }
-        cell
+        Cell::concat(self.iter().map(AsCell::as_cell))
     }
 }
 
@@ -296,11 +467,7 @@ impl<'c, T: Quotable + AsCell<'c>, const N: usize> AsCell<'c> for [T; N] {
 
 impl<'c, T: AsCell<'c>, const N: usize> ListIterator<'c, T> for [T; N] {
     fn iter_cells(&self) -> Cell<'c> {
-        let mut cell = Cell::nil();
-        for item in self {
-            cell.add(&item.as_cell());
-        }
-        cell
+        Cell::concat(self.iter().map(AsCell::as_cell))
     }
 }
 impl<'c> AsCell<'c> for Cell<'c> {
@@ -310,7 +477,7 @@ impl<'c> AsCell<'c> for Cell<'c> {
 }
 impl<'c> AsCell<'c> for &Cell<'c> {
     fn as_cell(&self) -> Cell<'c> {
-        UniquePointer::read_only(*self).read()
+        (*self).clone()
     }
 }
 
@@ -394,116 +561,72 @@ impl<'c> From<Cow<'c, str>> for Cell<'c> {
     }
 }
 
+
+/// Lists are equal when their elements and tails are, which is
+/// decided without looping forever on circular lists.
 impl<'c> PartialEq<Cell<'c>> for Cell<'c> {
     fn eq(&self, other: &Cell<'c>) -> bool {
-        if self.is_nil() && other.is_nil() {
-            return true;
-        }
-        let slen = self.len();
-        let olen = other.len();
-        if slen != olen {
-            return false;
-        }
-
-        let max_len = std::cmp::max(slen, olen);
-        let mut current = 0;
-
-        let mut iter = self
-            .clone()
-            .into_iter()
-            .zip(other.clone().into_iter());
-        loop {
-            current += 1;
-            match iter.next() {
-                Some((lhs, rhs)) =>
-                    if lhs != rhs {
-                        return false;
-                    },
-                None => return current < max_len,
-            }
-            if current == max_len {
-                break true;
-            }
-        }
+        circular::cells_equal(self, other)
     }
 }
+impl<'c> Eq for Cell<'c> {}
 
-impl<'c> Default for Cell<'c> {
-    fn default() -> Cell<'c> {
-        Cell::nil()
+impl<'c> PartialOrd for Cell<'c> {
+    fn partial_cmp(&self, other: &Cell<'c>) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
-
-/// [`Clone`] implementation for [`Cell`] clones the internal
-/// pointers.
-impl<'c> Clone for Cell<'c> {
-    fn clone(&self) -> Cell<'c> {
-        let mut cell = Cell::nil();
-        cell.refs = self.refs.clone();
-        cell.incr_ref();
-        if let Some(head) = self.head() {
-            cell.head.write(head)
-        }
-        if let Some(tail) = self.tail().map(Clone::clone) {
-            cell.tail.write(tail)
-        }
-        cell
+/// Lists are ordered by their elements, then by their dotted tails,
+/// which is decided without looping forever on circular lists.
+impl<'c> Ord for Cell<'c> {
+    fn cmp(&self, other: &Cell<'c>) -> Ordering {
+        circular::cmp_cells(self, other)
     }
 }
+
+/// Only the first elements of a list are hashed, so that circular
+/// lists can be hashed too.
 impl<'c> Hash for Cell<'c> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.head().hash(state);
-        self.tail().hash(state);
-        self.refs.hash(state);
-        self.quoted.hash(state);
+        circular::hash_cell(self, state);
     }
 }
+/// Drops the cells of a list one after the other rather than
+/// recursively, so that dropping a long list does not overflow the
+/// stack.
 impl<'c> Drop for Cell<'c> {
     fn drop(&mut self) {
-        self.dealloc();
+        let mut next = self.pair.take();
+        while let Some(pair) = next {
+            next = match Rc::try_unwrap(pair) {
+                Ok(pair) => match &mut pair.into_inner().cdr {
+                    Value::List(cell) | Value::QuotedList(cell) => cell.pair.take(),
+                    _ => None,
+                },
+                Err(_) => None,
+            };
+        }
     }
 }
 
 impl std::fmt::Debug for Cell<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.to_string())
+        write!(f, "{}", self)
     }
 }
 impl std::fmt::Display for Cell<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            if self.is_nil() {
-                "".to_string()
-            } else {
-                let mut parts = Vec::<String>::new();
-                if self.head.is_not_null() {
-                    parts.push(
-                        self.head()
-                            .map(|value| value.to_string())
-                            .unwrap_or_default(),
-                    )
-                }
-
-                if self.tail.is_not_null() {
-                    if let Some(tail) = self.tail() {
-                        parts.push(tail.to_string());
-                    }
-                }
-                parts.join(" ").trim().to_string()
-            }
-        )
+        write!(f, "{}", printer::display_items(self))
     }
 }
 
 impl<'c> AsValue<'c> for Cell<'c> {
     fn as_value(&self) -> Value<'c> {
-        if self.tail.is_null() {
+        if self.cdr().is_nil() {
             match self.head() {
                 Some(head) => {
                     let is_quoted = head.is_quoted();
-                    let value  = head.unwrap_list();
+                    let value = head.unwrap_list();
                     if is_quoted {
                         value.quote()
                     } else {
@@ -521,40 +644,33 @@ impl<'c> AsValue<'c> for Cell<'c> {
 }
 
 pub struct CellIterator<'c> {
-    cell: UniquePointer<Cell<'c>>,
+    cell: Cell<'c>,
 }
 
 impl<'c> CellIterator<'c> {
     pub fn new(cell: Cell<'c>) -> CellIterator<'c> {
-        CellIterator {
-            cell: UniquePointer::from_ref(&cell),
-        }
+        CellIterator { cell }
     }
 
     pub fn item(&self) -> Option<&Cell<'c>> {
-        self.cell.as_ref()
-    }
-
-    pub fn tail(&self) -> Option<&Cell<'c>> {
-        if let Some(cell) = self.cell.as_ref() {
-            cell.tail()
-        } else {
+        if self.cell.is_nil() {
             None
+        } else {
+            Some(&self.cell)
         }
     }
+
+    pub fn tail(&self) -> Option<Cell<'c>> {
+        self.cell.tail()
+    }
 }
 impl<'c> Iterator for CellIterator<'c> {
     type Item = Value<'c>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.cell.is_not_null() {
-            let value = self.cell.inner_ref().head();
-            let next_tail = self.cell.inner_ref().tail.clone();
-            self.cell = next_tail;
-            value
-        } else {
-            None
-        }
+        let value = self.cell.head()?;
+        self.cell = self.cell.tail().unwrap_or_default();
+        Some(value)
     }
 }
 
@@ -569,9 +685,10 @@ impl<'c> IntoIterator for Cell<'c> {
 
 impl<'c> FromIterator<Value<'c>> for Cell<'c> {
     fn from_iter<I: IntoIterator<Item = Value<'c>>>(iter: I) -> Cell<'c> {
+        let values = iter.into_iter().collect::<Vec<_>>();
         let mut cell = Cell::nil();
-        for value in iter {
-            cell.push_value(value);
+        for value in values.into_iter().rev() {
+            cell = Cell::cons(value, Value::List(cell));
         }
         cell
     }
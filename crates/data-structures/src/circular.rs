@@ -0,0 +1,258 @@
+//! Equality, ordering and hashing of lists and vectors that terminate
+//! on circular structure, such as a list whose last cdr is its first
+//! cell.
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::mem::discriminant;
+
+use crate::{Cell, Value, Vector};
+
+/// How many levels of nested lists and vectors [`hash_cell`] and
+/// [`hash_vector`] look into, like elisp `sxhash-equal`.
+const MAX_DEPTH: usize = 3;
+/// How many elements of each list or vector are hashed.
+const MAX_LENGTH: usize = 7;
+
+/// What two values must have in common to be equal.
+#[derive(Clone, Copy, PartialEq)]
+enum Equality {
+    /// Equal Rust values, the [`PartialEq`] of [`Value`]: quoted and
+    /// unquoted lists and symbols differ, and so do integers of
+    /// different types.
+    Structural,
+    /// elisp `equal`: lists, vectors and strings with equal contents,
+    /// other values [`Value::is_eql`].
+    Equal,
+}
+
+/// Pairs of cells or vectors under comparison, and what they are
+/// compared by.
+struct Assumed {
+    pairs: HashSet<(*const (), *const ())>,
+    equality: Equality,
+}
+
+impl Assumed {
+    fn new(equality: Equality) -> Assumed {
+        Assumed {
+            pairs: HashSet::new(),
+            equality,
+        }
+    }
+
+    fn insert(&mut self, pair: (*const (), *const ())) -> bool {
+        self.pairs.insert(pair)
+    }
+}
+
+/// `true` when the lists starting at `a` and `b` have equal elements
+/// and tails, however far they are unfolded.
+pub(crate) fn cells_equal<'c>(a: &Cell<'c>, b: &Cell<'c>) -> bool {
+    compare_cells(a, b, &mut Assumed::new(Equality::Structural))
+}
+
+/// `true` when `a` and `b` have equal elements, however far they are
+/// unfolded.
+pub(crate) fn vectors_equal<'c>(a: &Vector<'c>, b: &Vector<'c>) -> bool {
+    compare_vectors(a, b, &mut Assumed::new(Equality::Structural))
+}
+
+/// `true` when `a` and `b` are elisp `equal`, even when they are
+/// circular.
+pub(crate) fn values_equal<'c>(a: &Value<'c>, b: &Value<'c>) -> bool {
+    compare(a, b, &mut Assumed::new(Equality::Equal))
+}
+
+/// Compares `a` and `b` structurally, treating a pair of cells or
+/// vectors that is already being compared as equal: two circular
+/// structures differ only if a difference is found before they loop
+/// back.
+fn compare<'c>(a: &Value<'c>, b: &Value<'c>, assumed: &mut Assumed) -> bool {
+    match (a, b) {
+        (Value::List(a), Value::List(b)) | (Value::QuotedList(a), Value::QuotedList(b)) =>
+            compare_cells(a, b, assumed),
+        (Value::Vector(a), Value::Vector(b)) => compare_vectors(a, b, assumed),
+        _ if assumed.equality == Equality::Structural => a == b,
+        (Value::List(a) | Value::QuotedList(a), Value::List(b) | Value::QuotedList(b)) =>
+            compare_cells(a, b, assumed),
+        (Value::String(a), Value::String(b)) => a == b,
+        _ => a.is_eql(b),
+    }
+}
+
+fn compare_cells<'c>(a: &Cell<'c>, b: &Cell<'c>, assumed: &mut Assumed) -> bool {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    loop {
+        match (a.as_ptr(), b.as_ptr()) {
+            (None, None) => return true,
+            (Some(x), Some(y)) =>
+                if std::ptr::eq(x, y) || !assumed.insert((x, y)) {
+                    return true;
+                },
+            _ => return false,
+        }
+        if !compare(&a.head().unwrap_or_default(), &b.head().unwrap_or_default(), assumed) {
+            return false;
+        }
+        match (&a.cdr(), &b.cdr()) {
+            (Value::List(x), Value::List(y)) => {
+                a = x.clone();
+                b = y.clone();
+            },
+            (x, y) => return compare(x, y, assumed),
+        }
+    }
+}
+
+fn compare_vectors<'c>(a: &Vector<'c>, b: &Vector<'c>, assumed: &mut Assumed) -> bool {
+    if a.ptr_eq(b) || !assumed.insert((a.as_ptr(), b.as_ptr())) {
+        return true;
+    }
+    let (a, b) = (a.values(), b.values());
+    a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| compare(a, b, assumed))
+}
+
+/// Orders `a` and `b` by their elements, like slices, treating a pair
+/// of vectors that is already being compared as equal.
+pub(crate) fn cmp_vectors<'c>(a: &Vector<'c>, b: &Vector<'c>) -> Ordering {
+    order_vectors(a, b, &mut HashSet::new())
+}
+
+/// Orders the lists starting at `a` and `b` by their elements, then
+/// by their dotted tails, treating a pair of cells that is already
+/// being compared as equal.
+pub(crate) fn cmp_cells<'c>(a: &Cell<'c>, b: &Cell<'c>) -> Ordering {
+    order_cells(a, b, &mut HashSet::new())
+}
+
+/// Orders `a` and `b` like the derived [`Ord`] of [`Value`], looking
+/// into the lists and vectors nested in them with [`cmp_cells`] and
+/// [`cmp_vectors`].
+fn order<'c>(a: &Value<'c>, b: &Value<'c>, ordering: &mut HashSet<(*const (), *const ())>) -> Ordering {
+    match (a, b) {
+        (Value::List(a), Value::List(b)) | (Value::QuotedList(a), Value::QuotedList(b)) =>
+            order_cells(a, b, ordering),
+        (Value::Vector(a), Value::Vector(b)) => order_vectors(a, b, ordering),
+        _ => a.cmp(b),
+    }
+}
+
+fn order_cells<'c>(
+    a: &Cell<'c>,
+    b: &Cell<'c>,
+    ordering: &mut HashSet<(*const (), *const ())>,
+) -> Ordering {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    loop {
+        match (a.as_ptr(), b.as_ptr()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) =>
+                if std::ptr::eq(x, y) || !ordering.insert((x, y)) {
+                    return Ordering::Equal;
+                },
+        }
+        match order(&a.head().unwrap_or_default(), &b.head().unwrap_or_default(), ordering) {
+            Ordering::Equal => {},
+            ordering => return ordering,
+        }
+        // the shorter list first, then the one without a dotted tail
+        match (&a.cdr(), &b.cdr()) {
+            (Value::List(x), Value::List(y)) => {
+                a = x.clone();
+                b = y.clone();
+            },
+            (_, Value::List(_)) => return Ordering::Less,
+            (Value::List(_), _) => return Ordering::Greater,
+            (Value::Nil, Value::Nil) => return Ordering::Equal,
+            (Value::Nil, _) => return Ordering::Less,
+            (_, Value::Nil) => return Ordering::Greater,
+            (x, y) => return order(x, y, ordering),
+        }
+    }
+}
+
+fn order_vectors<'c>(
+    a: &Vector<'c>,
+    b: &Vector<'c>,
+    ordering: &mut HashSet<(*const (), *const ())>,
+) -> Ordering {
+    if a.ptr_eq(b) || !ordering.insert((a.as_ptr(), b.as_ptr())) {
+        return Ordering::Equal;
+    }
+    let (a, b) = (a.values(), b.values());
+    for (a, b) in a.iter().zip(b.iter()) {
+        match order(a, b, ordering) {
+            Ordering::Equal => {},
+            ordering => return ordering,
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// Hashes the first elements of the list starting at `cell`, and of
+/// the lists and vectors nested in them, so that lists equal by
+/// [`cells_equal`] hash the same even when they are circular.
+pub(crate) fn hash_cell<H: Hasher>(cell: &Cell, state: &mut H) {
+    hash_elements(cell, Equality::Structural, MAX_DEPTH, state)
+}
+
+/// Hashes the first elements of `vector` like [`hash_cell`].
+pub(crate) fn hash_vector<H: Hasher>(vector: &Vector, state: &mut H) {
+    hash_items(vector, Equality::Structural, MAX_DEPTH, state)
+}
+
+/// Hashes `value` so that values equal by [`values_equal`] hash the
+/// same, like elisp `sxhash-equal`.
+pub(crate) fn hash_equal<H: Hasher>(value: &Value, state: &mut H) {
+    hash_value(value, Equality::Equal, MAX_DEPTH, state)
+}
+
+fn hash_value<H: Hasher>(value: &Value, equality: Equality, depth: usize, state: &mut H) {
+    match (value, equality) {
+        (Value::List(cell) | Value::QuotedList(cell), Equality::Structural) => {
+            discriminant(value).hash(state);
+            hash_elements(cell, equality, depth, state);
+        },
+        (Value::List(cell) | Value::QuotedList(cell), Equality::Equal) => {
+            discriminant(&Value::List(Cell::nil())).hash(state);
+            hash_elements(cell, equality, depth, state);
+        },
+        (Value::Vector(vector), _) => {
+            discriminant(value).hash(state);
+            hash_items(vector, equality, depth, state);
+        },
+        (atom, Equality::Structural) => atom.hash(state),
+        (Value::String(string), Equality::Equal) => string.hash(state),
+        (atom, Equality::Equal) => atom.hash_eql(state),
+    }
+}
+
+fn hash_elements<H: Hasher>(cell: &Cell, equality: Equality, depth: usize, state: &mut H) {
+    if depth == 0 {
+        return;
+    }
+    let mut cell = cell.clone();
+    for _ in 0..MAX_LENGTH {
+        match cell.head() {
+            Some(head) => hash_value(&head, equality, depth - 1, state),
+            None => return,
+        }
+        match &cell.cdr() {
+            Value::List(next) => cell = next.clone(),
+            tail => return hash_value(tail, equality, depth - 1, state),
+        }
+    }
+}
+
+fn hash_items<H: Hasher>(vector: &Vector, equality: Equality, depth: usize, state: &mut H) {
+    if depth == 0 {
+        return;
+    }
+    vector.len().hash(state);
+    for item in vector.values().iter().take(MAX_LENGTH) {
+        hash_value(item, equality, depth - 1, state);
+    }
+}
@@ -2,26 +2,28 @@
 use minilisp_util::dbg;
 
 use crate::{AsCell, AsValue, Cell, ListIterator, Quotable, Symbol, Value};
-pub fn cons<'c, T: AsCell<'c>>(head: T, tail: &mut Cell<'c>) -> Cell<'c> {
-    let mut head = head.as_cell();
-    head.add(tail);
+/// Prepends the elements of `head` to `tail`, which is shared rather
+/// than copied.
+///
+/// `O(1)` in the length of `tail`
+pub fn cons<'c, T: AsCell<'c>>(head: T, tail: &Cell<'c>) -> Cell<'c> {
+    let mut head = Cell::from_iter(head.as_cell().values());
+    head.set_dotted_tail(Value::List(tail.clone()));
     head
 }
 pub fn append<'c, T: ListIterator<'c, Value<'c>>>(list: T) -> Value<'c> {
     let is_quoted = list.is_quoted();
-    let mut items = Cell::nil();
+    let mut items = Vec::new();
     for value in list {
         match &value {
-            Value::List(h) | Value::QuotedList(h) =>
-                for item in h.clone().into_iter() {
-                    items.push_value(item.clone());
-                },
+            Value::List(h) | Value::QuotedList(h) => items.extend(h.values()),
             Value::EmptyList | Value::EmptyQuotedList | Value::Nil => {},
             _ => {
-                items.push_value(value);
+                items.push(value);
             },
         }
     }
+    let items = Cell::from_iter(items);
     if is_quoted {
         Value::quoted_list(items)
     } else {
@@ -31,6 +33,9 @@ pub fn append<'c, T: ListIterator<'c, Value<'c>>>(list: T) -> Value<'c> {
 pub fn makelist<'c>(value: Value<'c>, count: usize) -> Value<'c> {
     (0..count).map(|_| value.clone()).collect()
 }
+/// The first element of the list `value`, `nil` for anything else.
+///
+/// `O(1)`
 pub fn car<'c>(value: &Value<'c>) -> Value<'c> {
     let is_quoted = value.is_quoted();
     let value = match value {
@@ -42,24 +47,194 @@ pub fn car<'c>(value: &Value<'c>) -> Value<'c> {
     } else {value}
 }
 
+/// The rest of the list `item` after its first element, sharing its
+/// cells.
+///
+/// `O(1)`
 pub fn cdr<'c>(item: &Value<'c>) -> Value<'c> {
     match item {
-        Value::List(ref h) | Value::QuotedList(ref h) => h
-            .tail()
-            .map(|cell| Value::list(cell.clone()))
-            .unwrap_or_default(),
+        Value::List(ref h) | Value::QuotedList(ref h) => h.cdr(),
         _ => Value::Nil,
     }
 }
-pub fn list<'c, T: ListIterator<'c, Value<'c>>>(list: T) -> Value<'c> {
-    // dbg!(&list);
-    let mut cell = Cell::nil();
-    for item in list.into_iter() {
-        // dbg!(&item);
-        cell.push_value(item);
+/// Builds the cons cell `(car . cdr)`: `cdr` is spliced in when it
+/// is a list or `nil`, any other value yields an improper list.
+pub fn pair<'c>(car: Value<'c>, cdr: Value<'c>) -> Value<'c> {
+    Value::List(Cell::cons(car, cdr))
+}
+/// `true` when `value` is a cons cell, that is, a non-empty list.
+pub fn consp<'c>(value: &Value<'c>) -> bool {
+    match value {
+        Value::List(h) | Value::QuotedList(h) => !h.is_nil(),
+        _ => false,
     }
-    Value::List(cell)
+}
+/// `true` when `value` is a cons cell or `nil`.
+pub fn listp<'c>(value: &Value<'c>) -> bool {
+    matches!(
+        value,
+        Value::List(_)
+            | Value::QuotedList(_)
+            | Value::Nil
+            | Value::EmptyList
+            | Value::EmptyQuotedList
+    )
+}
+/// Returns the length of `value` when it is a proper list, as
+/// elisp's `proper-list-p` does, or `None` otherwise.
+pub fn proper_list_p<'c>(value: &Value<'c>) -> Option<usize> {
+    match value {
+        Value::List(h) | Value::QuotedList(h) =>
+            if h.is_proper() {
+                Some(h.len())
+            } else {
+                None
+            },
+        Value::Nil | Value::EmptyList | Value::EmptyQuotedList => Some(0),
+        _ => None,
+    }
+}
+pub fn list<'c, T: ListIterator<'c, Value<'c>>>(list: T) -> Value<'c> {
+    Value::List(Cell::from_iter(list))
 }
 
-pub fn setcar<'c>(cell: &Cell<'c>, sym: &Symbol, value: &Value) {}
-pub fn setcdr<'c>(cell: &Cell<'c>, value: &Value) {}
+/// Replaces the car of the cons cell `cell` with `value` in place,
+/// as seen by every list sharing it, and returns `value`, or `None`
+/// when `cell` is not a cons cell.
+pub fn setcar<'c>(cell: &Value<'c>, value: Value<'c>) -> Option<Value<'c>> {
+    match cell {
+        Value::List(h) | Value::QuotedList(h) if h.set_car(value.clone()) => Some(value),
+        _ => None,
+    }
+}
+/// Replaces the cdr of the cons cell `cell` with `value` in place and
+/// returns `value`, or `None` when `cell` is not a cons cell.
+pub fn setcdr<'c>(cell: &Value<'c>, value: Value<'c>) -> Option<Value<'c>> {
+    match cell {
+        Value::List(h) | Value::QuotedList(h) if h.set_cdr(value.clone()) => Some(value),
+        _ => None,
+    }
+}
+/// Concatenates `lists` by setting the cdr of the last cell of each
+/// one to the next, like elisp `nconc`. Empty lists are skipped and
+/// the last argument may be any value.
+///
+/// Returns `None` when an argument other than the last is not a
+/// list.
+pub fn nconc<'c, I: IntoIterator<Item = Value<'c>>>(lists: I) -> Option<Value<'c>> {
+    let mut result = Value::Nil;
+    let mut last: Option<Cell<'c>> = None;
+    let mut lists = lists.into_iter().peekable();
+    while let Some(list) = lists.next() {
+        let is_last = lists.peek().is_none();
+        let cell = match &list {
+            Value::List(h) | Value::QuotedList(h) if !h.is_nil() => h.clone(),
+            _ if is_last => Cell::nil(),
+            Value::Nil
+            | Value::EmptyList
+            | Value::EmptyQuotedList
+            | Value::List(_)
+            | Value::QuotedList(_) => continue,
+            _ => return None,
+        };
+        match &last {
+            Some(last) => {
+                last.set_cdr(list.clone());
+            },
+            None => result = list.unquote(),
+        }
+        if !cell.is_nil() {
+            last = Some(cell.last());
+        }
+    }
+    Some(result)
+}
+/// Reverses the proper list `list` in place by reversing the cdr of
+/// each cell, like elisp `nreverse`, and returns its former last
+/// cell. Returns `None` when `list` is not a proper list.
+pub fn nreverse<'c>(list: &Value<'c>) -> Option<Value<'c>> {
+    let mut cell = match list {
+        Value::List(h) | Value::QuotedList(h) if h.is_proper() => h.unquote(),
+        Value::Nil | Value::EmptyList | Value::EmptyQuotedList => return Some(Value::Nil),
+        _ => return None,
+    };
+    let mut reversed = Value::Nil;
+    while !cell.is_nil() {
+        let next = cell.tail().unwrap_or_default();
+        cell.set_cdr(reversed);
+        reversed = Value::List(cell);
+        cell = next;
+    }
+    Some(reversed)
+}
+/// Removes every element `eq` to `element` from `list` by splicing
+/// the cdrs around them, like elisp `delq`, and returns the list
+/// starting at the first cell kept. Returns `None` when `list` is
+/// not a list.
+pub fn delq<'c>(element: &Value<'c>, list: &Value<'c>) -> Option<Value<'c>> {
+    let mut head = match list {
+        Value::List(h) | Value::QuotedList(h) => h.unquote(),
+        Value::Nil | Value::EmptyList | Value::EmptyQuotedList => return Some(Value::Nil),
+        _ => return None,
+    };
+    while head.head().is_some_and(|value| value.is_eq(element)) {
+        head = head.tail().unwrap_or_default();
+    }
+    let mut previous = head.clone();
+    while let Some(next) = previous.tail() {
+        if next.head().is_some_and(|value| value.is_eq(element)) {
+            previous.set_cdr(next.cdr());
+        } else {
+            previous = next;
+        }
+    }
+    Some(if head.is_nil() {
+        Value::Nil
+    } else {
+        Value::List(head)
+    })
+}
+/// The value that follows the first `property` that is `eq` to
+/// `property` in the property list `plist`, like elisp `plist-get`.
+/// Returns `None` when `plist` has no such property.
+pub fn plist_get<'c>(plist: &Value<'c>, property: &Value<'c>) -> Option<Value<'c>> {
+    let mut cell = match plist {
+        Value::List(h) | Value::QuotedList(h) => h.clone(),
+        _ => return None,
+    };
+    while let (Some(key), Some(next)) = (cell.head(), cell.tail()) {
+        if key.is_eq(property) {
+            return next.head();
+        }
+        cell = next.tail()?;
+    }
+    None
+}
+/// Sets the value of `property` in the property list `plist` to
+/// `value`, like elisp `plist-put`: in place when `plist` has the
+/// property already, otherwise by appending it. Returns the
+/// property list, which is a new list when `plist` was empty.
+pub fn plist_put<'c>(plist: &Value<'c>, property: Value<'c>, value: Value<'c>) -> Value<'c> {
+    let mut cell = match plist {
+        Value::List(h) | Value::QuotedList(h) if !h.is_nil() => h.clone(),
+        _ => return list([property, value]),
+    };
+    loop {
+        let Some(next) = cell.tail() else {
+            break;
+        };
+        if cell.head().is_some_and(|key| key.is_eq(&property)) {
+            next.set_car(value);
+            return plist.clone();
+        }
+        match next.tail() {
+            Some(following) => cell = following,
+            None => {
+                cell = next;
+                break;
+            },
+        }
+    }
+    cell.set_cdr(list([property, value]));
+    plist.clone()
+}
@@ -0,0 +1,96 @@
+//! The equality predicates of elisp, from identity to structure:
+//! [`Value::is_eq`], [`Value::is_eql`] and [`Value::is_equal`].
+//!
+//! The [`PartialEq`] of [`Value`] is Rust equality instead, which
+//! tells quoted values and integers of different types apart.
+use std::hash::{Hash, Hasher};
+use std::mem::discriminant;
+
+use crate::{circular, Number, Value};
+
+impl<'c> Value<'c> {
+    /// `true` when `self` and `other` are the same object, like elisp
+    /// `eq`: the same list, vector, string or hash table, the same
+    /// symbol quoted or not, or `nil` however it is written.
+    ///
+    /// Numbers are not boxed, so they are compared like
+    /// [`Value::is_eql`].
+    pub fn is_eq(&self, other: &Value<'c>) -> bool {
+        match (self, other) {
+            (a, b) if a.is_empty() || b.is_empty() => a.is_empty() && b.is_empty(),
+            (Value::List(a) | Value::QuotedList(a), Value::List(b) | Value::QuotedList(b)) =>
+                a.ptr_eq(b),
+            (Value::Vector(a), Value::Vector(b)) => a.ptr_eq(b),
+            (Value::String(a), Value::String(b)) => a.ptr_eq(b),
+            (Value::HashTable(a), Value::HashTable(b)) => a.ptr_eq(b),
+            (Value::Symbol(a) | Value::QuotedSymbol(a), Value::Symbol(b) | Value::QuotedSymbol(b)) =>
+                a.unquote() == b.unquote(),
+            (a, b) => match (Number::from_value(a), Number::from_value(b)) {
+                (Some(a), Some(b)) => numbers_eql(&a, &b),
+                (None, None) => a == b,
+                _ => false,
+            },
+        }
+    }
+
+    /// `true` when `self` and `other` are [`Value::is_eq`], like elisp
+    /// `eql`: numbers of the same type with the same value, where
+    /// integers of any size are one type and floats are compared by
+    /// their bits, so that `0.0` and `-0.0` differ and NaN is `eql`
+    /// to itself.
+    pub fn is_eql(&self, other: &Value<'c>) -> bool {
+        self.is_eq(other)
+    }
+
+    /// `true` when `self` and `other` are lists, vectors or strings
+    /// with equal contents, or other values that are
+    /// [`Value::is_eql`], like elisp `equal`. Circular lists are
+    /// compared without looping forever.
+    pub fn is_equal(&self, other: &Value<'c>) -> bool {
+        circular::values_equal(self, other)
+    }
+
+    /// Hashes `self` so that values that are [`Value::is_eql`] hash
+    /// the same.
+    pub fn hash_eql<H: Hasher>(&self, state: &mut H) {
+        if self.is_empty() {
+            return discriminant(&Value::Nil).hash(state);
+        }
+        match self {
+            Value::List(cell) | Value::QuotedList(cell) => cell.as_ptr().hash(state),
+            Value::Vector(vector) => vector.as_ptr().hash(state),
+            Value::String(string) => string.as_ptr().hash(state),
+            Value::HashTable(table) => table.as_ptr().hash(state),
+            Value::Symbol(symbol) | Value::QuotedSymbol(symbol) => symbol.unquote().hash(state),
+            value => match Number::from_value(value) {
+                Some(Number::Float(float)) => float.to_bits().hash(state),
+                Some(Number::Rational(rational)) => rational.hash(state),
+                // integers of every type as an i64 when they fit
+                Some(Number::UnsignedInteger(integer)) => (integer as i64).hash(state),
+                Some(Number::Integer(integer)) => integer.hash(state),
+                Some(Number::BigInteger(integer)) => match integer.to_i64() {
+                    Some(integer) => integer.hash(state),
+                    None => integer.hash(state),
+                },
+                None => value.hash(state),
+            },
+        }
+    }
+
+    /// Hashes `self` so that values that are [`Value::is_equal`] hash
+    /// the same, looking into the first elements of lists and vectors
+    /// only, like elisp `sxhash-equal`.
+    pub fn hash_equal<H: Hasher>(&self, state: &mut H) {
+        circular::hash_equal(self, state)
+    }
+}
+
+fn numbers_eql(a: &Number, b: &Number) -> bool {
+    match (a, b) {
+        (Number::Float(a), Number::Float(b)) => a.to_bits() == b.to_bits(),
+        (Number::Float(_), _) | (_, Number::Float(_)) => false,
+        (Number::Rational(a), Number::Rational(b)) => a == b,
+        (Number::Rational(_), _) | (_, Number::Rational(_)) => false,
+        (a, b) => a.compare(b) == Some(std::cmp::Ordering::Equal),
+    }
+}
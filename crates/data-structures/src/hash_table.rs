@@ -0,0 +1,246 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use crate::{printer, Quotable, Value};
+
+/// How a [`HashTable`] compares its keys, the `:test` of elisp
+/// `make-hash-table`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Test {
+    /// The same object: lists, vectors, strings and hash tables by
+    /// identity, other values by value. Numbers are not boxed, so
+    /// this is the same as `eql`.
+    Eq,
+    /// Like `eq`, comparing floats by their bits.
+    #[default]
+    Eql,
+    /// Structurally equal, like elisp `equal`.
+    Equal,
+}
+
+impl Test {
+    /// The test named `name`: `eq`, `eql` or `equal`.
+    pub fn from_name(name: &str) -> Option<Test> {
+        match name {
+            "eq" => Some(Test::Eq),
+            "eql" => Some(Test::Eql),
+            "equal" => Some(Test::Equal),
+            _ => None,
+        }
+    }
+
+    /// `true` when `a` and `b` pass the test, by [`Value::is_eq`],
+    /// [`Value::is_eql`] or [`Value::is_equal`].
+    pub fn matches<'c>(&self, a: &Value<'c>, b: &Value<'c>) -> bool {
+        match self {
+            Test::Eq => a.is_eq(b),
+            Test::Eql => a.is_eql(b),
+            Test::Equal => a.is_equal(b),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Test::Eq => "eq",
+            Test::Eql => "eql",
+            Test::Equal => "equal",
+        }
+    }
+}
+
+/// A key along with the test that compares and hashes it.
+struct Key<'c> {
+    value: Value<'c>,
+    test: Test,
+}
+
+impl<'c> PartialEq for Key<'c> {
+    fn eq(&self, other: &Key<'c>) -> bool {
+        self.test.matches(&self.value, &other.value)
+    }
+}
+impl<'c> Eq for Key<'c> {}
+
+impl<'c> Hash for Key<'c> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self.test {
+            Test::Equal => self.value.hash_equal(state),
+            Test::Eq | Test::Eql => self.value.hash_eql(state),
+        }
+    }
+}
+
+struct Table<'c> {
+    test: Test,
+    /// Position of each key in `entries`.
+    positions: HashMap<Key<'c>, usize>,
+    /// Entries in insertion order, `None` where one was removed.
+    entries: Vec<Option<(Value<'c>, Value<'c>)>>,
+}
+
+impl<'c> Table<'c> {
+    fn key(&self, value: &Value<'c>) -> Key<'c> {
+        Key {
+            value: value.unquote(),
+            test: self.test,
+        }
+    }
+
+    /// Drops the slots of removed entries once they outnumber the
+    /// entries left.
+    fn compact(&mut self) {
+        if self.entries.len() <= 2 * self.positions.len() + 8 {
+            return;
+        }
+        self.entries.retain(Option::is_some);
+        for (position, entry) in self.entries.iter().enumerate() {
+            let (key, _) = entry.as_ref().expect("entry");
+            let key = Key {
+                value: key.clone(),
+                test: self.test,
+            };
+            self.positions.insert(key, position);
+        }
+    }
+}
+
+/// Mutable map from keys to values, compared by a [`Test`], like an
+/// elisp hash table.
+///
+/// Clones share the same table, so [`HashTable::insert`] is visible
+/// through every copy. Entries are visited in insertion order. Two
+/// hash tables are equal only when they are the same table, as with
+/// elisp `equal`.
+#[derive(Clone)]
+pub struct HashTable<'c> {
+    table: Rc<RefCell<Table<'c>>>,
+}
+
+impl<'c> HashTable<'c> {
+    pub fn new(test: Test) -> HashTable<'c> {
+        HashTable {
+            table: Rc::new(RefCell::new(Table {
+                test,
+                positions: HashMap::new(),
+                entries: Vec::new(),
+            })),
+        }
+    }
+
+    pub fn test(&self) -> Test {
+        self.table.borrow().test
+    }
+
+    /// `O(1)`
+    pub fn len(&self) -> usize {
+        self.table.borrow().positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The value stored under `key`.
+    ///
+    /// `O(1)`
+    pub fn get(&self, key: &Value<'c>) -> Option<Value<'c>> {
+        let table = self.table.borrow();
+        let position = *table.positions.get(&table.key(key))?;
+        table.entries[position].as_ref().map(|(_, value)| value.clone())
+    }
+
+    /// Stores `value` under `key`, returning the value it replaces.
+    /// A replaced entry keeps its place in the insertion order.
+    ///
+    /// `O(1)`
+    pub fn insert(&self, key: Value<'c>, value: Value<'c>) -> Option<Value<'c>> {
+        let mut table = self.table.borrow_mut();
+        let key = table.key(&key);
+        if let Some(&position) = table.positions.get(&key) {
+            let (_, previous) = table.entries[position].as_mut().expect("entry");
+            return Some(std::mem::replace(previous, value));
+        }
+        let position = table.entries.len();
+        table.entries.push(Some((key.value.clone(), value)));
+        table.positions.insert(key, position);
+        None
+    }
+
+    /// Removes the entry of `key`, returning its value.
+    ///
+    /// `O(1)` amortized
+    pub fn remove(&self, key: &Value<'c>) -> Option<Value<'c>> {
+        let mut table = self.table.borrow_mut();
+        let key = table.key(key);
+        let position = table.positions.remove(&key)?;
+        let (_, value) = table.entries[position].take().expect("entry");
+        table.compact();
+        Some(value)
+    }
+
+    pub fn clear(&self) {
+        let mut table = self.table.borrow_mut();
+        table.positions.clear();
+        table.entries.clear();
+    }
+
+    /// Copies of the keys, in insertion order.
+    pub fn keys(&self) -> Vec<Value<'c>> {
+        self.entries().into_iter().map(|(key, _)| key).collect()
+    }
+
+    /// Copies of the entries, in insertion order, so that the table
+    /// can be modified while they are visited.
+    pub fn entries(&self) -> Vec<(Value<'c>, Value<'c>)> {
+        self.table.borrow().entries.iter().flatten().cloned().collect()
+    }
+
+    /// `true` when `self` and `other` are the same table.
+    pub fn ptr_eq(&self, other: &HashTable<'c>) -> bool {
+        Rc::ptr_eq(&self.table, &other.table)
+    }
+
+    /// Address of the table, identifying it while it is alive.
+    pub(crate) fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(&self.table).cast()
+    }
+}
+
+impl<'c> PartialEq for HashTable<'c> {
+    fn eq(&self, other: &HashTable<'c>) -> bool {
+        self.ptr_eq(other)
+    }
+}
+impl<'c> Eq for HashTable<'c> {}
+
+impl<'c> PartialOrd for HashTable<'c> {
+    fn partial_cmp(&self, other: &HashTable<'c>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<'c> Ord for HashTable<'c> {
+    fn cmp(&self, other: &HashTable<'c>) -> Ordering {
+        self.as_ptr().cmp(&other.as_ptr())
+    }
+}
+
+impl<'c> Hash for HashTable<'c> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ptr().hash(state);
+    }
+}
+
+impl Display for HashTable<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", printer::display(&Value::HashTable(self.clone())))
+    }
+}
+impl Debug for HashTable<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
@@ -2,12 +2,22 @@
 pub mod traits;
 pub use traits::{AsNumber, ListValue, Quotable};
 pub mod cons;
-pub use cons::{append, car, cdr, list, cons, makelist, setcar, setcdr};
+pub use cons::{append, car, cdr, consp, delq, list, listp, cons, makelist, nconc, nreverse, pair, plist_get, plist_put, proper_list_p, setcar, setcdr};
 pub mod cell;
-pub use cell::{AsCell, Cell, ListIterator};
+pub use cell::{AsCell, Cell, ListIterator, WeakCell};
 pub mod value;
-pub use value::{AsValue, Float, Integer, UnsignedInteger, Value, AsFloat, AsInteger, AsUnsignedInteger, ValueIterator};
+pub use value::{escape_char, escape_string, ArithmeticError, AsValue, BigInteger, Float, Integer, Number, Rational, UnsignedInteger, Value, AsFloat, AsInteger, AsUnsignedInteger, ValueIterator};
+pub mod printer;
+pub use printer::{prin1, princ, Printer};
+mod circular;
+mod equality;
+pub mod vector;
+pub use vector::Vector;
+pub mod hash_table;
+pub use hash_table::HashTable;
 pub mod symbol;
 pub use symbol::{AsSymbol, Symbol};
+pub mod text;
+pub use text::Text;
 pub mod macros;
 pub mod test;
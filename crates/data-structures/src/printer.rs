@@ -0,0 +1,370 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::hash_table::Test;
+use crate::{escape_char, escape_string, Cell, HashTable, Value, Vector};
+
+/// Prints `value` readably, like elisp `prin1`: strings, characters
+/// and symbols are escaped so that reading the output back yields
+/// `value`.
+///
+/// The reader only produces one representation of some values, so
+/// `Value::Byte` and a `Value::Integer` that fits in `u32` read back
+/// as `Value::UnsignedInteger`, and `Value::EmptyList` and
+/// `Value::EmptyQuotedList` read back as empty lists.
+pub fn prin1(value: &Value) -> String {
+    Printer {
+        readable: true,
+        circle: false,
+    }
+    .print(value)
+}
+
+/// Prints `value` for humans, like elisp `princ`: strings and
+/// characters are written as they are and symbols are not escaped.
+pub fn princ(value: &Value) -> String {
+    Printer::default().print(value)
+}
+
+/// Options of the printer behind [`prin1`] and [`princ`].
+///
+/// A list or vector that contains itself is printed as `#N`, `N`
+/// being how many lists and vectors deep it was entered, and a list
+/// whose cdr loops back to one of its cells ends with `. #N`, `N`
+/// being the index of that cell, unless `circle` is set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Printer {
+    /// Escape strings, characters and symbols like [`prin1`] rather
+    /// than writing them as they are like [`princ`].
+    pub readable: bool,
+    /// Label every list and vector reached more than once with `#N=`
+    /// where it is first printed and write `#N#` in its other
+    /// places, like elisp `print-circle`: `#1=(a . #1#)`.
+    pub circle: bool,
+}
+
+impl Printer {
+    pub fn print(&self, value: &Value) -> String {
+        let style = if self.readable {
+            Style::Readable
+        } else {
+            Style::Human
+        };
+        let mut state = State::new(style, self.circle);
+        if self.circle {
+            state.find_shared(value);
+        }
+        state.print(value);
+        state.output
+    }
+}
+
+/// Prints `value` the way `Display for Value` writes atoms.
+pub(crate) fn display(value: &Value) -> String {
+    let mut state = State::new(Style::Display, false);
+    state.print(value);
+    state.output
+}
+
+/// Prints the elements of the list starting at `cell`, without the
+/// surrounding parentheses, for `Display for Cell`.
+pub(crate) fn display_items(cell: &Cell) -> String {
+    let mut state = State::new(Style::Display, false);
+    if let Some(id) = cell.as_ptr() {
+        state.path.push(id);
+        state.print_items(cell);
+    }
+    state.output
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Readable,
+    Human,
+    Display,
+}
+
+struct State {
+    style: Style,
+    circle: bool,
+    /// Lists and vectors reached more than once, with their label
+    /// once printed, when `circle` is set.
+    labels: HashMap<*const (), Option<usize>>,
+    /// How many labels were printed.
+    printed_labels: usize,
+    /// Lists and vectors being printed, outermost first.
+    path: Vec<*const ()>,
+    output: String,
+}
+
+impl State {
+    fn new(style: Style, circle: bool) -> State {
+        State {
+            style,
+            circle,
+            labels: HashMap::new(),
+            printed_labels: 0,
+            path: Vec::new(),
+            output: String::new(),
+        }
+    }
+
+    /// Walks `value` once, without recursing, to find the lists and
+    /// vectors that need a label.
+    fn find_shared(&mut self, value: &Value) {
+        let mut seen = HashSet::new();
+        let mut pending = vec![value.clone()];
+        while let Some(value) = pending.pop() {
+            match &value {
+                Value::List(cell) | Value::QuotedList(cell) => {
+                    let mut cell = cell.clone();
+                    while let Some(id) = cell.as_ptr() {
+                        if !seen.insert(id) {
+                            self.labels.insert(id, None);
+                            break;
+                        }
+                        pending.push(cell.head().unwrap_or_default());
+                        match &cell.cdr() {
+                            Value::List(next) => cell = next.clone(),
+                            tail => {
+                                pending.push(tail.clone());
+                                break;
+                            },
+                        }
+                    }
+                },
+                Value::Vector(vector) =>
+                    if seen.insert(vector.as_ptr()) {
+                        pending.extend(vector.values());
+                    } else {
+                        self.labels.insert(vector.as_ptr(), None);
+                    },
+                Value::HashTable(table) =>
+                    if seen.insert(table.as_ptr()) {
+                        for (key, value) in table.entries() {
+                            pending.push(key);
+                            pending.push(value);
+                        }
+                    } else {
+                        self.labels.insert(table.as_ptr(), None);
+                    },
+                _ => {},
+            }
+        }
+    }
+
+    fn print(&mut self, value: &Value) {
+        match value {
+            Value::List(cell) => self.print_list(cell),
+            Value::QuotedList(cell) => {
+                self.output.push('\'');
+                self.print_list(cell);
+            },
+            Value::Vector(vector) => self.print_vector(vector),
+            Value::HashTable(table) => self.print_hash_table(table),
+            atom => self.print_atom(atom),
+        }
+    }
+
+    /// Writes `#N#` or `#N` and returns `true` when the list or
+    /// vector `id` was already printed, or writes its `#N=` label if
+    /// it needs one.
+    fn print_reference(&mut self, id: *const ()) -> bool {
+        if self.circle {
+            match self.labels.get_mut(&id) {
+                Some(Some(label)) => {
+                    self.output.push_str(&format!("#{}#", label));
+                    true
+                },
+                Some(label) => {
+                    self.printed_labels += 1;
+                    *label = Some(self.printed_labels);
+                    self.output.push_str(&format!("#{}=", self.printed_labels));
+                    false
+                },
+                None => false,
+            }
+        } else {
+            let depth = self.path.iter().position(|&entered| std::ptr::eq(entered, id));
+            if let Some(depth) = depth {
+                self.output.push_str(&format!("#{}", depth));
+            }
+            depth.is_some()
+        }
+    }
+
+    fn print_list(&mut self, cell: &Cell) {
+        let Some(id) = cell.as_ptr() else {
+            self.output.push_str("()");
+            return;
+        };
+        if self.print_reference(id) {
+            return;
+        }
+        self.path.push(id);
+        self.output.push('(');
+        self.print_items(cell);
+        self.output.push(')');
+        self.path.pop();
+    }
+
+    fn print_items(&mut self, cell: &Cell) {
+        let mut indexes = HashMap::new();
+        let mut cell = cell.clone();
+        while let Some(id) = cell.as_ptr() {
+            indexes.insert(id, indexes.len());
+            self.print(&cell.head().unwrap_or_default());
+            match &cell.cdr() {
+                Value::List(next) => {
+                    let Some(next_id) = next.as_ptr() else { break };
+                    if self.circle && self.labels.contains_key(&next_id) {
+                        self.output.push_str(" . ");
+                        self.print_list(next);
+                        break;
+                    }
+                    if let Some(index) = indexes.get(&next_id).filter(|_| !self.circle) {
+                        self.output.push_str(&format!(" . #{}", index));
+                        break;
+                    }
+                    self.output.push(' ');
+                    cell = next.clone();
+                },
+                Value::Nil => break,
+                tail => {
+                    self.output.push_str(" . ");
+                    self.print(tail);
+                    break;
+                },
+            }
+        }
+    }
+
+    fn print_vector(&mut self, vector: &Vector) {
+        let id = vector.as_ptr();
+        if self.print_reference(id) {
+            return;
+        }
+        self.path.push(id);
+        self.output.push('[');
+        for (index, item) in vector.values().iter().enumerate() {
+            if index > 0 {
+                self.output.push(' ');
+            }
+            self.print(item);
+        }
+        self.output.push(']');
+        self.path.pop();
+    }
+
+    /// Writes `#s(hash-table test equal data (key value...))`, the
+    /// test being left out when it is `eql`.
+    fn print_hash_table(&mut self, table: &HashTable) {
+        let id = table.as_ptr();
+        if self.print_reference(id) {
+            return;
+        }
+        self.path.push(id);
+        self.output.push_str("#s(hash-table");
+        if table.test() != Test::Eql {
+            self.output.push_str(" test ");
+            self.output.push_str(table.test().name());
+        }
+        let entries = table.entries();
+        if !entries.is_empty() {
+            self.output.push_str(" data (");
+            for (index, (key, value)) in entries.iter().enumerate() {
+                if index > 0 {
+                    self.output.push(' ');
+                }
+                self.print(key);
+                self.output.push(' ');
+                self.print(value);
+            }
+            self.output.push(')');
+        }
+        self.output.push(')');
+        self.path.pop();
+    }
+
+    fn print_atom(&mut self, value: &Value) {
+        let readable = self.style == Style::Readable;
+        let output = &mut self.output;
+        match value {
+            _ if self.style == Style::Display => output.push_str(&value.to_string()),
+            Value::Nil => output.push_str("nil"),
+            Value::T => output.push('t'),
+            Value::String(string) if readable => {
+                output.push('"');
+                output.push_str(&escape_string(string));
+                output.push('"');
+            },
+            Value::String(string) => output.push_str(string),
+            Value::Char(c) if readable => output.push_str(&escape_char(*c)),
+            Value::Char(c) => output.push(*c),
+            Value::Symbol(symbol) => print_symbol(symbol.symbol(), readable, output),
+            Value::QuotedSymbol(symbol) => {
+                output.push('\'');
+                print_symbol(symbol.symbol(), readable, output);
+            },
+            Value::Byte(byte) => output.push_str(&byte.to_string()),
+            Value::UnsignedInteger(integer) => output.push_str(&integer.to_string()),
+            Value::Integer(integer) => output.push_str(&integer.to_string()),
+            Value::Float(float) => output.push_str(&print_float(**float)),
+            Value::BigInteger(integer) => output.push_str(&integer.to_string()),
+            Value::Rational(rational) => output.push_str(&rational.to_string()),
+            Value::EmptyList => output.push_str("()"),
+            Value::EmptyQuotedList => output.push_str("'()"),
+            Value::List(_) | Value::QuotedList(_) | Value::Vector(_) | Value::HashTable(_) =>
+                unreachable!("printed by State::print"),
+        }
+    }
+}
+
+/// Writes `name` with a backslash before every character that the
+/// reader would otherwise take as a delimiter, a number, a
+/// character literal or a dispatch.
+fn print_symbol(name: &str, readable: bool, output: &mut String) {
+    if !readable {
+        output.push_str(name);
+        return;
+    }
+    if matches!(name, "nil" | "t" | ".") || looks_like_number(name) {
+        output.push('\\');
+    }
+    for (index, c) in name.chars().enumerate() {
+        if c.is_whitespace()
+            || matches!(c, '(' | ')' | '[' | ']' | '"' | ';' | '\'' | '`' | ',' | '\\')
+            || (index == 0 && matches!(c, '?' | '#'))
+        {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+}
+
+fn looks_like_number(name: &str) -> bool {
+    if let Some((numerator, denominator)) = name.split_once('/') {
+        let digits = |text: &str| !text.is_empty() && text.chars().all(|c| c.is_ascii_digit());
+        let numerator = numerator.strip_prefix(['+', '-']).unwrap_or(numerator);
+        return digits(numerator) && digits(denominator);
+    }
+    let mantissa = name
+        .strip_suffix("e+INF")
+        .or_else(|| name.strip_suffix("e+NaN"))
+        .unwrap_or(name);
+    mantissa.chars().all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'))
+        && mantissa.parse::<f64>().is_ok()
+}
+
+/// Writes `float` so that it reads back as a float, e.g. `1.0`
+/// rather than `1`, with `1.0e+INF` and `0.0e+NaN` for infinities
+/// and NaN.
+fn print_float(float: f64) -> String {
+    let sign = if float.is_sign_negative() { "-" } else { "" };
+    if float.is_nan() {
+        format!("{}0.0e+NaN", sign)
+    } else if float.is_infinite() {
+        format!("{}1.0e+INF", sign)
+    } else {
+        format!("{:?}", float)
+    }
+}
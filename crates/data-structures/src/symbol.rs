@@ -1,17 +1,67 @@
 #![allow(unused)]
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::sync::{LazyLock, RwLock};
 
 use unique_pointer::UniquePointer;
 
-use crate::{AsValue, Quotable, Value};
+use crate::{plist_get, plist_put, AsValue, Quotable, Value};
 
 pub trait AsSymbol<'c> {
     fn as_symbol(&self) -> Symbol<'c>;
 }
 
-#[derive(Clone, PartialOrd, Ord, Default, PartialEq, Eq, Hash)]
+/// Every symbol name ever built, each stored once and identified by
+/// its index.
+#[derive(Default)]
+struct Interner {
+    names: Vec<&'static str>,
+    ids: HashMap<&'static str, u32>,
+}
+
+static INTERNER: LazyLock<RwLock<Interner>> = LazyLock::new(|| {
+    let mut interner = Interner::default();
+    interner.intern("");
+    RwLock::new(interner)
+});
+
+thread_local! {
+    /// Property list of each interned symbol that has one, by id.
+    ///
+    /// The interner is shared by every thread and holds names only,
+    /// whereas values cannot leave their thread, so property lists are
+    /// kept per thread, as owned copies that borrow no source text.
+    static PLISTS: RefCell<HashMap<u32, Value<'static>>> = RefCell::new(HashMap::new());
+}
+
+impl Interner {
+    fn intern(&mut self, name: &str) -> (u32, &'static str) {
+        if let Some(id) = self.ids.get(name) {
+            return (*id, self.names[*id as usize]);
+        }
+        let name: &'static str = name.to_string().leak();
+        let id = u32::try_from(self.names.len()).expect("too many symbols");
+        self.names.push(name);
+        self.ids.insert(name, id);
+        (id, name)
+    }
+}
+
+/// A symbol name interned in a global table.
+///
+/// Building a symbol looks its name up and only stores names not seen
+/// before, so equality and hashing compare ids rather than strings.
+/// Ordering compares names.
+///
+/// Every symbol of the same name shares one property list, read and
+/// written with [`Symbol::get`] and [`Symbol::put`].
+#[derive(Clone, Default)]
 pub struct Symbol<'c> {
+    id: u32,
     sym: &'c str,
     quoted: bool,
 }
@@ -21,10 +71,32 @@ impl<'c> Symbol<'c> {
     }
 
     pub fn quoted<T: ToString>(sym: T, quoted: bool) -> Symbol<'c> {
-        Symbol {
-            sym: sym.to_string().leak(),
-            quoted,
-        }
+        let sym = sym.to_string();
+        let symbol = Symbol::interned(&sym).unwrap_or_else(|| {
+            let (id, sym) = INTERNER.write().expect("interner").intern(&sym);
+            Symbol {
+                id,
+                sym,
+                quoted: false,
+            }
+        });
+        Symbol { quoted, ..symbol }
+    }
+
+    /// The symbol named `sym` if any symbol of that name was built
+    /// before, like elisp `intern-soft`.
+    pub fn interned(sym: &str) -> Option<Symbol<'c>> {
+        let interner = INTERNER.read().expect("interner");
+        interner.ids.get(sym).map(|id| Symbol {
+            id: *id,
+            sym: interner.names[*id as usize],
+            quoted: false,
+        })
+    }
+
+    /// Index of the name of this symbol in the interner.
+    pub fn id(&self) -> u32 {
+        self.id
     }
 
     pub fn symbol(&self) -> &'c str {
@@ -32,16 +104,97 @@ impl<'c> Symbol<'c> {
     }
 
     pub fn quote(&self) -> Symbol<'c> {
-        Symbol::quoted(self.symbol(), true)
+        Symbol {
+            quoted: true,
+            ..self.clone()
+        }
     }
 
     pub fn unquote(&self) -> Symbol<'c> {
-        Symbol::quoted(self.symbol(), false)
+        Symbol {
+            quoted: false,
+            ..self.clone()
+        }
     }
 
     pub fn is_quoted(&self) -> bool {
         self.quoted
     }
+
+    /// The property list of this symbol, `nil` when it has none, like
+    /// elisp `symbol-plist`.
+    pub fn plist(&self) -> Value<'c> {
+        PLISTS.with(|plists| {
+            plists.borrow().get(&self.id).map(Value::to_owned).unwrap_or_default()
+        })
+    }
+
+    /// Replaces the property list of this symbol with a copy of
+    /// `plist`, like elisp `setplist`.
+    pub fn set_plist(&self, plist: &Value) {
+        let plist = plist.unquote().to_owned();
+        PLISTS.with(|plists| plists.borrow_mut().insert(self.id, plist));
+    }
+
+    /// The value of `property` in the property list of this symbol,
+    /// `nil` when it has none, like elisp `get`.
+    pub fn get(&self, property: &Value) -> Value<'c> {
+        let property = property.unquote().to_owned();
+        PLISTS.with(|plists| {
+            plists
+                .borrow()
+                .get(&self.id)
+                .and_then(|plist| plist_get(plist, &property))
+                .map(|value| value.to_owned())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Sets `property` to a copy of `value` in the property list of
+    /// this symbol, like elisp `put`.
+    pub fn put(&self, property: &Value, value: &Value) {
+        let (property, value) = (property.unquote().to_owned(), value.unquote().to_owned());
+        PLISTS.with(|plists| {
+            let mut plists = plists.borrow_mut();
+            let plist = plists.remove(&self.id).unwrap_or_default();
+            plists.insert(self.id, plist_put(&plist, property, value));
+        });
+    }
+
+    /// `true` when the name starts with `:`, as in `:test`. Keywords
+    /// evaluate to themselves and cannot be bound.
+    pub fn is_keyword(&self) -> bool {
+        self.sym.starts_with(':')
+    }
+}
+
+impl<'c> PartialEq for Symbol<'c> {
+    fn eq(&self, other: &Symbol<'c>) -> bool {
+        self.id == other.id && self.quoted == other.quoted
+    }
+}
+impl<'c> Eq for Symbol<'c> {}
+
+impl<'c> PartialOrd for Symbol<'c> {
+    fn partial_cmp(&self, other: &Symbol<'c>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<'c> Ord for Symbol<'c> {
+    fn cmp(&self, other: &Symbol<'c>) -> Ordering {
+        if self.id == other.id {
+            self.quoted.cmp(&other.quoted)
+        } else {
+            self.sym.cmp(other.sym)
+        }
+    }
+}
+
+impl<'c> Hash for Symbol<'c> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.quoted.hash(state);
+    }
 }
 
 impl Display for Symbol<'_> {
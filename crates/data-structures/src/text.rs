@@ -0,0 +1,140 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// Contents of a string value: text borrowed from the input it was
+/// read from, or text of its own shared by every clone.
+///
+/// Equality, ordering and hashing compare the text, so a borrowed
+/// string equals an owned one with the same contents.
+#[derive(Clone)]
+pub enum Text<'c> {
+    Borrowed(&'c str),
+    Shared(Rc<str>),
+}
+
+impl<'c> Text<'c> {
+    /// Copies `text` into storage of its own.
+    pub fn new<T: ToString>(text: T) -> Text<'c> {
+        Text::Shared(Rc::from(text.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Text::Borrowed(text) => text,
+            Text::Shared(text) => text,
+        }
+    }
+
+    /// Copies borrowed text into storage of its own, so that it
+    /// borrows nothing. Owned text is shared rather than copied.
+    pub fn to_owned<'o>(&self) -> Text<'o> {
+        match self {
+            Text::Borrowed(text) => Text::new(text),
+            Text::Shared(text) => Text::Shared(text.clone()),
+        }
+    }
+
+    /// `true` when both are the same text in memory rather than
+    /// equal text, like elisp `eq` on strings.
+    pub fn ptr_eq(&self, other: &Text<'c>) -> bool {
+        std::ptr::eq(self.as_str(), other.as_str())
+    }
+
+    /// Address of the text, identifying it while it is alive.
+    pub(crate) fn as_ptr(&self) -> *const u8 {
+        self.as_str().as_ptr()
+    }
+}
+
+impl Default for Text<'_> {
+    fn default() -> Self {
+        Text::Borrowed("")
+    }
+}
+
+impl Deref for Text<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Text<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'c> From<&'c str> for Text<'c> {
+    fn from(text: &'c str) -> Text<'c> {
+        Text::Borrowed(text)
+    }
+}
+
+impl<'c> From<String> for Text<'c> {
+    fn from(text: String) -> Text<'c> {
+        Text::Shared(Rc::from(text))
+    }
+}
+
+impl<'c> From<Cow<'c, str>> for Text<'c> {
+    fn from(text: Cow<'c, str>) -> Text<'c> {
+        match text {
+            Cow::Borrowed(text) => Text::Borrowed(text),
+            Cow::Owned(text) => Text::from(text),
+        }
+    }
+}
+
+impl PartialEq for Text<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+impl Eq for Text<'_> {}
+
+impl PartialEq<str> for Text<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Text<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for Text<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Text<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for Text<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl Display for Text<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl Debug for Text<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
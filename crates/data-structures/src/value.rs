@@ -1,5 +1,6 @@
 #![allow(unused)]
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::convert::{AsMut, AsRef};
 use std::fmt::{Debug, Display, Formatter};
 use std::iter::{Extend, FromIterator, IntoIterator};
@@ -14,8 +15,14 @@ pub use float::{AsFloat, Float};
 pub mod unsigned_integer;
 use minilisp_util::dbg;
 pub use unsigned_integer::{AsUnsignedInteger, UnsignedInteger};
+pub mod big_integer;
+pub use big_integer::BigInteger;
+pub mod rational;
+pub use rational::Rational;
+pub mod number;
+pub use number::{ArithmeticError, Number};
 
-use crate::{AsCell, AsNumber, AsSymbol, Cell, ListIterator, Quotable, Symbol};
+use crate::{AsCell, AsNumber, AsSymbol, Cell, HashTable, ListIterator, Quotable, Symbol, Text, Vector};
 
 pub trait ValueListIterator<'c>: IntoIterator<Item = Value<'c>> + Quotable {}
 // impl <'c, T: IntoIterator<Item = Value<'c>> + Quotable> ValueListIterator<'c> for T {}
@@ -29,15 +36,20 @@ pub enum Value<'c> {
     #[default]
     Nil,
     T,
-    String(&'c str),
+    String(Text<'c>),
+    Char(char),
     Symbol(Symbol<'c>),
     QuotedSymbol(Symbol<'c>),
     Byte(u8),
     UnsignedInteger(UnsignedInteger),
     Integer(Integer),
     Float(Float),
+    BigInteger(BigInteger),
+    Rational(Rational),
     List(Cell<'c>),
     QuotedList(Cell<'c>),
+    Vector(Vector<'c>),
+    HashTable(HashTable<'c>),
     EmptyList,
     EmptyQuotedList,
 }
@@ -59,7 +71,11 @@ impl<'c> Value<'c> {
     }
 
     pub fn string<T: ToString>(value: T) -> Value<'c> {
-        Value::String(value.to_string().leak())
+        Value::String(Text::new(value))
+    }
+
+    pub fn char(c: char) -> Value<'c> {
+        Value::Char(c)
     }
 
     pub fn byte<T: AsNumber<u8>>(byte: T) -> Value<'c> {
@@ -90,6 +106,23 @@ impl<'c> Value<'c> {
         Value::QuotedList(item.as_cell().quote())
     }
 
+    pub fn vector<I: IntoIterator<Item = Value<'c>>>(items: I) -> Value<'c> {
+        Value::Vector(Vector::from_iter(items))
+    }
+
+    /// Copies `self` into a value that borrows nothing, so that it
+    /// can outlive the text it was read from, with any lifetime.
+    ///
+    /// Lists, vectors and hash tables are copied deeply, each one
+    /// once: a list, vector or hash table reached twice is the same
+    /// copy both times, so shared structure stays shared and cycles
+    /// lead back into the copy. Symbols are interned already, strings
+    /// read from text are copied into storage of their own and other
+    /// strings are shared.
+    pub fn to_owned<'o>(&self) -> Value<'o> {
+        Copies::default().value(self)
+    }
+
     pub fn is_nil(&self) -> bool {
         if *self == Value::Nil {
             true
@@ -113,6 +146,7 @@ impl<'c> Value<'c> {
         match self {
             Value::List(h) => h.len(),
             Value::QuotedList(h) => h.len(),
+            Value::Vector(h) => h.len(),
             Value::EmptyList => 0,
             Value::EmptyQuotedList => 0,
             Value::Nil => 0,
@@ -157,7 +191,7 @@ impl<'c> Value<'c> {
     pub fn tail(&self) -> Cell<'c> {
         match self {
             Value::List(cell) | Value::QuotedList(cell) =>
-                cell.tail().map(Clone::clone).unwrap_or_default(),
+                cell.tail().unwrap_or_default(),
             _ => Cell::nil(),
         }
     }
@@ -173,7 +207,7 @@ impl<'c> Value<'c> {
     pub fn unwrap_list(&self) -> Value<'c> {
         match self {
             Value::List(cell) | Value::QuotedList(cell) =>
-                if cell.tail.is_null() {
+                if cell.cdr().is_nil() {
                     let value = cell.head().unwrap_or_default();
                     value.clone()
                 } else {
@@ -204,6 +238,18 @@ impl<'c> Value<'c> {
         }
     }
 
+    pub fn is_big_integer(&self) -> bool {
+        matches!(self, Value::BigInteger(_))
+    }
+
+    pub fn is_rational(&self) -> bool {
+        matches!(self, Value::Rational(_))
+    }
+
+    pub fn is_number(&self) -> bool {
+        Number::from_value(self).is_some()
+    }
+
     pub fn is_string(&self) -> bool {
         match self {
             Value::String(_) => true,
@@ -211,6 +257,10 @@ impl<'c> Value<'c> {
         }
     }
 
+    pub fn is_char(&self) -> bool {
+        matches!(self, Value::Char(_))
+    }
+
     pub fn is_symbol(&self) -> bool {
         match self {
             Value::Symbol(_) => true,
@@ -219,6 +269,14 @@ impl<'c> Value<'c> {
         }
     }
 
+    pub fn is_vector(&self) -> bool {
+        matches!(self, Value::Vector(_))
+    }
+
+    pub fn is_hash_table(&self) -> bool {
+        matches!(self, Value::HashTable(_))
+    }
+
     pub fn is_list(&self) -> bool {
         match self {
             Value::List(_) => true,
@@ -235,7 +293,108 @@ impl<'c> AsValue<'c> for Value<'c> {
 }
 impl<'c> AsValue<'c> for &Value<'c> {
     fn as_value(&self) -> Value<'c> {
-        UniquePointer::read_only(*self).read()
+        (*self).clone()
+    }
+}
+
+/// Copies made by [`Value::to_owned`] of the lists, vectors and hash
+/// tables met so far, by address of the original.
+#[derive(Default)]
+struct Copies<'o> {
+    cells: HashMap<*const (), Cell<'o>>,
+    vectors: HashMap<*const (), Vector<'o>>,
+    hash_tables: HashMap<*const (), HashTable<'o>>,
+}
+
+impl<'o> Copies<'o> {
+    fn value(&mut self, value: &Value) -> Value<'o> {
+        match value {
+            Value::Nil => Value::Nil,
+            Value::T => Value::T,
+            Value::String(string) => Value::String(string.to_owned()),
+            Value::Char(c) => Value::Char(*c),
+            Value::Symbol(symbol) => Value::Symbol(Symbol::new(symbol.symbol())),
+            Value::QuotedSymbol(symbol) => Value::quoted_symbol(symbol.symbol()),
+            Value::Byte(byte) => Value::Byte(*byte),
+            Value::UnsignedInteger(integer) => Value::UnsignedInteger(*integer),
+            Value::Integer(integer) => Value::Integer(*integer),
+            Value::Float(float) => Value::Float(*float),
+            Value::BigInteger(integer) => Value::BigInteger(integer.clone()),
+            Value::Rational(rational) => Value::Rational(rational.clone()),
+            Value::List(cell) => Value::List(self.cell(cell)),
+            Value::QuotedList(cell) => Value::QuotedList(self.cell(cell)),
+            Value::Vector(vector) => Value::Vector(self.vector(vector)),
+            Value::HashTable(table) => Value::HashTable(self.hash_table(table)),
+            Value::EmptyList => Value::EmptyList,
+            Value::EmptyQuotedList => Value::EmptyQuotedList,
+        }
+    }
+
+    /// Copies the list `cell` one cell at a time along its cdrs,
+    /// recording each copy before copying its car, so that a car or
+    /// cdr leading back to a cell copied already links to its copy.
+    fn cell(&mut self, cell: &Cell) -> Cell<'o> {
+        let Some(address) = cell.as_ptr() else {
+            return Cell::quoted(Option::<Value<'o>>::None, cell.is_quoted());
+        };
+        if let Some(copy) = self.cells.get(&address) {
+            let mut copy = copy.clone();
+            copy.set_quoted(cell.is_quoted());
+            return copy;
+        }
+        let mut head = Cell::cons(Value::Nil, Value::Nil);
+        self.cells.insert(address, head.clone());
+        let (mut original, mut copy) = (cell.clone(), head.clone());
+        loop {
+            let car = self.value(&original.head().unwrap_or_default());
+            copy.set_car(car);
+            let cdr = original.cdr();
+            let next = match &cdr {
+                Value::List(next) | Value::QuotedList(next) => next.clone(),
+                _ => {
+                    let tail = self.value(&cdr);
+                    copy.set_cdr(tail);
+                    break;
+                },
+            };
+            let Some(address) = next.as_ptr() else {
+                break;
+            };
+            if let Some(next) = self.cells.get(&address) {
+                copy.set_cdr(Value::List(next.clone()));
+                break;
+            }
+            let next_copy = Cell::cons(Value::Nil, Value::Nil);
+            self.cells.insert(address, next_copy.clone());
+            copy.set_cdr(Value::List(next_copy.clone()));
+            (original, copy) = (next, next_copy);
+        }
+        head.set_quoted(cell.is_quoted());
+        head
+    }
+
+    fn vector(&mut self, vector: &Vector) -> Vector<'o> {
+        if let Some(copy) = self.vectors.get(&vector.as_ptr()) {
+            return copy.clone();
+        }
+        let copy = Vector::new(vec![Value::Nil; vector.len()]);
+        self.vectors.insert(vector.as_ptr(), copy.clone());
+        for (index, item) in vector.values().iter().enumerate() {
+            copy.set(index, self.value(item));
+        }
+        copy
+    }
+
+    fn hash_table(&mut self, table: &HashTable) -> HashTable<'o> {
+        if let Some(copy) = self.hash_tables.get(&table.as_ptr()) {
+            return copy.clone();
+        }
+        let copy = HashTable::new(table.test());
+        self.hash_tables.insert(table.as_ptr(), copy.clone());
+        for (key, value) in table.entries() {
+            copy.insert(self.value(&key), self.value(&value));
+        }
+        copy
     }
 }
 
@@ -243,6 +402,49 @@ impl<'c> Drop for Value<'c> {
     fn drop(&mut self) {}
 }
 
+/// Escapes `string` with the escape sequences understood by the
+/// reader, without the surrounding double quotes.
+pub fn escape_string(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\0' => escaped.push_str("\\0"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes `c` as a `?x` character literal understood by the
+/// reader.
+pub fn escape_char(c: char) -> String {
+    match c {
+        '\n' => "?\\n".to_string(),
+        '\t' => "?\\t".to_string(),
+        '\r' => "?\\r".to_string(),
+        '\x07' => "?\\a".to_string(),
+        '\x08' => "?\\b".to_string(),
+        '\x0b' => "?\\v".to_string(),
+        '\x0c' => "?\\f".to_string(),
+        '\x1b' => "?\\e".to_string(),
+        ' ' => "?\\s".to_string(),
+        '\x7f' => "?\\d".to_string(),
+        '\x01'..='\x1a' => format!("?\\C-{}", ((c as u8) + 0x60) as char),
+        '\x1c' | '\x1d' => format!("?\\^\\{}", ((c as u8) + 0x40) as char),
+        '\0'..='\x1f' => format!("?\\^{}", ((c as u8) + 0x40) as char),
+        '(' | ')' | '[' | ']' | '\\' | ';' | '"' | '\'' | '`' | '#' | '?' | ',' | '.' =>
+            format!("?\\{}", c),
+        c if c.is_control() => format!("?\\x{:x}", c as u32),
+        c => format!("?{}", c),
+    }
+}
+
 impl Display for Value<'_> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         write!(
@@ -253,8 +455,11 @@ impl Display for Value<'_> {
                 Value::Nil => "nil".to_string(),
                 Value::Byte(h) => format!("0x{:02x}", h),
                 Value::Float(h) => format!("{}", h),
+                Value::BigInteger(h) => format!("{}", h),
+                Value::Rational(h) => format!("{}", h),
                 Value::Integer(h) => format!("{}", h),
-                Value::String(h) => format!("{:#?}", h),
+                Value::String(h) => format!("\"{}\"", escape_string(h)),
+                Value::Char(h) => escape_char(*h),
                 Value::Symbol(h) => format!("{}", h),
                 Value::QuotedSymbol(h) => format!("'{}", h),
                 Value::UnsignedInteger(h) => format!("{}", h),
@@ -272,6 +477,8 @@ impl Display for Value<'_> {
                         format!("'({})", h)
                     }
                 },
+                Value::Vector(h) => format!("{}", h),
+                Value::HashTable(h) => format!("{}", h),
                 Value::EmptyList => format!("()"),
                 Value::EmptyQuotedList => format!("'()"),
             }
@@ -288,13 +495,18 @@ impl Debug for Value<'_> {
                 Value::Nil => "nil".to_string(),
                 Value::Byte(h) => format!("0x{:02x}", h),
                 Value::Float(h) => format!("{:#?}", h),
+                Value::BigInteger(h) => format!("{:#?}", h),
+                Value::Rational(h) => format!("{:#?}", h),
                 Value::Integer(h) => format!("{:#?}", h),
-                Value::String(h) => format!("{:#?}", h),
+                Value::String(h) => format!("\"{}\"", escape_string(h)),
+                Value::Char(h) => escape_char(*h),
                 Value::Symbol(h) => format!("{}", h),
                 Value::QuotedSymbol(h) => format!("'{:#?}", h),
                 Value::UnsignedInteger(h) => format!("{:#?}", h),
                 Value::List(h) => format!("({:#?})", h),
                 Value::QuotedList(h) => format!("'({:#?})", h),
+                Value::Vector(h) => format!("{:#?}", h),
+                Value::HashTable(h) => format!("{:#?}", h),
                 Value::EmptyList => format!("()"),
                 Value::EmptyQuotedList => format!("'()"),
             }
@@ -320,6 +532,16 @@ impl<'c> From<u8> for Value<'c> {
         Value::Byte(value)
     }
 }
+impl<'c> From<char> for Value<'c> {
+    fn from(value: char) -> Value<'c> {
+        Value::Char(value)
+    }
+}
+impl<'c> From<Vector<'c>> for Value<'c> {
+    fn from(value: Vector<'c>) -> Value<'c> {
+        Value::Vector(value)
+    }
+}
 impl<'c> From<Symbol<'c>> for Value<'c> {
     fn from(value: Symbol<'c>) -> Value<'c> {
         if value.is_quoted() {
@@ -340,7 +562,7 @@ impl<'c> From<&Symbol<'c>> for Value<'c> {
 }
 impl<'c> From<&'c str> for Value<'c> {
     fn from(value: &'c str) -> Value<'c> {
-        Value::String(value.to_string().leak())
+        Value::string(value)
     }
 }
 impl<'c> From<u64> for Value<'c> {
@@ -364,17 +586,17 @@ impl<'c> From<i32> for Value<'c> {
 
 impl<'c> From<Cow<'c, str>> for Value<'c> {
     fn from(value: Cow<'c, str>) -> Value<'c> {
-        Value::from(value.into_owned().to_string().leak())
+        Value::String(Text::from(value))
     }
 }
 impl<'c> From<&'c mut str> for Value<'c> {
     fn from(value: &'c mut str) -> Value<'c> {
-        Value::String(value.to_string().leak())
+        Value::string(value)
     }
 }
 impl<'c> From<String> for Value<'c> {
     fn from(value: String) -> Value<'c> {
-        Value::String(value.leak())
+        Value::String(Text::from(value))
     }
 }
 impl<'c> From<Option<String>> for Value<'c> {
@@ -471,7 +693,7 @@ impl<'c> AsValue<'c> for u8 {
 // }
 impl<'c> AsValue<'c> for &'c str {
     fn as_value(&self) -> Value<'c> {
-        Value::String(self.to_string().leak())
+        Value::string(self)
     }
 }
 impl<'c> AsValue<'c> for u64 {
@@ -495,17 +717,17 @@ impl<'c> AsValue<'c> for i32 {
 
 impl<'c> AsValue<'c> for Cow<'c, str> {
     fn as_value(&self) -> Value<'c> {
-        Value::from(self.clone().into_owned().to_string().leak())
+        Value::String(Text::from(self.clone()))
     }
 }
 impl<'c> AsValue<'c> for &'c mut str {
     fn as_value(&self) -> Value<'c> {
-        Value::String(self.to_string().leak())
+        Value::string(self)
     }
 }
 impl<'c> AsValue<'c> for String {
     fn as_value(&self) -> Value<'c> {
-        Value::String(self.to_string().leak())
+        Value::string(self)
     }
 }
 impl<'c> AsValue<'c> for Option<String> {
@@ -523,16 +745,16 @@ impl<'c> AsCell<'c> for Value<'c> {
             Value::Symbol(h) => Cell::quoted(Some(h.unquote()), false),
             Value::QuotedSymbol(h) => Cell::quoted(Some(h.quote()), true),
             Value::List(h) => {
-                let mut cell = Cell::nil();
-                for item in h.clone().into_iter() {
-                    cell.add(&Cell::new(item));
+                let mut cell = Cell::from_iter(h.values());
+                if let Some(dotted_tail) = h.dotted_tail() {
+                    cell.set_dotted_tail(dotted_tail);
                 }
                 cell
             },
             Value::QuotedList(h) => {
-                let mut cell = Cell::nil();
-                for item in h.clone().into_iter() {
-                    cell.add(&Cell::new(item));
+                let mut cell = Cell::from_iter(h.values());
+                if let Some(dotted_tail) = h.dotted_tail() {
+                    cell.set_dotted_tail(dotted_tail);
                 }
                 cell.quote()
             },
@@ -542,8 +764,7 @@ impl<'c> AsCell<'c> for Value<'c> {
 }
 impl<'c> AsCell<'c> for &Value<'c> {
     fn as_cell(&self) -> Cell<'c> {
-        let value = UniquePointer::read_only(*self).read();
-        value.as_cell()
+        (*self).as_cell()
     }
 }
 
@@ -558,42 +779,37 @@ impl<'c> ListIterator<'c, Value<'c>> for Value<'c> {
 
 #[derive(Debug, Clone)]
 pub struct ValueIterator<'c> {
-    cell: UniquePointer<Cell<'c>>,
+    cell: Cell<'c>,
     quoted: bool,
 }
 
 impl<'c> ValueIterator<'c> {
     pub fn new(cell: &Cell<'c>, quoted: bool) -> ValueIterator<'c> {
         ValueIterator {
-            cell: UniquePointer::from_ref(cell),
+            cell: cell.clone(),
             quoted,
         }
     }
 
     pub fn item(&self) -> Option<&Cell<'c>> {
-        self.cell.as_ref()
-    }
-
-    pub fn tail(&self) -> Option<&Cell<'c>> {
-        if let Some(cell) = self.cell.as_ref() {
-            cell.tail()
-        } else {
+        if self.cell.is_nil() {
             None
+        } else {
+            Some(&self.cell)
         }
     }
+
+    pub fn tail(&self) -> Option<Cell<'c>> {
+        self.cell.tail()
+    }
 }
 impl<'c> Iterator for ValueIterator<'c> {
     type Item = Value<'c>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.cell.is_not_null() {
-            let value = self.cell.inner_ref().head();
-            let next_tail = self.cell.inner_ref().tail.clone();
-            self.cell = next_tail;
-            value
-        } else {
-            None
-        }
+        let value = self.cell.head()?;
+        self.cell = self.cell.tail().unwrap_or_default();
+        Some(value)
     }
 }
 impl<'c> Quotable for ValueIterator<'c> {
@@ -628,46 +844,23 @@ impl<'c> IntoIterator for Value<'c> {
 
 impl<'c> FromIterator<Value<'c>> for Value<'c> {
     fn from_iter<I: IntoIterator<Item = Value<'c>>>(iter: I) -> Value<'c> {
-        let mut cell = Cell::nil();
-        for value in iter {
-            cell.push_value(value);
-        }
-        Value::list(cell)
+        Value::list(Cell::from_iter(iter))
     }
 }
 impl<'c> Extend<Value<'c>> for Value<'c> {
     fn extend<T: IntoIterator<Item = Value<'c>>>(&mut self, iter: T) {
+        let items = Cell::from_iter(iter);
+        if items.is_nil() && matches!(self, Value::List(_) | Value::QuotedList(_)) {
+            return;
+        }
         if let Value::List(ref mut cell) = self {
-            for value in iter {
-                cell.push_value(value);
-            }
+            cell.set_dotted_tail(Value::List(items));
         } else if let Value::QuotedList(ref mut cell) = self {
-            for value in iter {
-                cell.push_value(value);
-            }
+            cell.set_dotted_tail(Value::List(items));
         } else {
             match self.clone() {
-                Value::EmptyList => {
-                    let mut cell = Cell::nil();
-                    for value in iter {
-                        cell.push_value(value)
-                    }
-                    *self = Value::List(cell)
-                },
-                Value::EmptyQuotedList => {
-                    let mut cell = Cell::nil();
-                    for value in iter {
-                        cell.push_value(value)
-                    }
-                    *self = Value::QuotedList(cell)
-                },
-                value => {
-                    let mut cell = Cell::nil();
-                    for value in iter {
-                        cell.push_value(value)
-                    }
-                    *self = Value::List(cell)
-                },
+                Value::EmptyQuotedList => *self = Value::QuotedList(items),
+                value => *self = Value::List(items),
             }
         }
     }
@@ -690,19 +883,19 @@ impl<'c> Quotable for Value<'c> {
         match self {
             Value::Symbol(h) =>
                 if quoted {
-                    *self = Value::QuotedSymbol(h.clone())
+                    *self = Value::QuotedSymbol(h.quote())
                 },
             Value::List(h) =>
                 if quoted {
-                    *self = Value::QuotedList(h.clone())
+                    *self = Value::QuotedList(h.quote())
                 },
             Value::QuotedSymbol(h) =>
                 if !quoted {
-                    *self = Value::Symbol(h.clone())
+                    *self = Value::Symbol(h.unquote())
                 },
             Value::QuotedList(h) =>
                 if !quoted {
-                    *self = Value::List(h.clone())
+                    *self = Value::List(h.unquote())
                 },
             Value::EmptyList =>
                 if quoted {
@@ -716,6 +909,18 @@ impl<'c> Quotable for Value<'c> {
         }
     }
 
+    fn quote(&self) -> Value<'c> {
+        let mut value = self.clone();
+        value.set_quoted(true);
+        value
+    }
+
+    fn unquote(&self) -> Value<'c> {
+        let mut value = self.clone();
+        value.set_quoted(false);
+        value
+    }
+
     fn is_quoted(&self) -> bool {
         match self {
             Value::Symbol(h) => false,
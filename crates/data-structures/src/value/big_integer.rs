@@ -0,0 +1,369 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+/// Integer of any size, which arithmetic falls back to when a result
+/// does not fit in an `i64`.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInteger {
+    negative: bool,
+    /// Base 2³² digits, least significant first, without leading
+    /// zeros, so that zero has none.
+    digits: Vec<u32>,
+}
+
+impl BigInteger {
+    pub fn zero() -> BigInteger {
+        BigInteger::default()
+    }
+
+    pub fn one() -> BigInteger {
+        BigInteger::from(1i64)
+    }
+
+    fn from_digits(negative: bool, mut digits: Vec<u32>) -> BigInteger {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        BigInteger {
+            negative: negative && !digits.is_empty(),
+            digits,
+        }
+    }
+
+    /// `2^exponent`
+    pub fn power_of_two(exponent: usize) -> BigInteger {
+        let mut digits = vec![0u32; exponent / 32 + 1];
+        digits[exponent / 32] = 1 << (exponent % 32);
+        BigInteger::from_digits(false, digits)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// How many bits the magnitude of `self` takes.
+    pub fn bits(&self) -> usize {
+        match self.digits.last() {
+            Some(last) => self.digits.len() * 32 - last.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    pub fn abs(&self) -> BigInteger {
+        BigInteger {
+            negative: false,
+            digits: self.digits.clone(),
+        }
+    }
+
+    /// `self` as an `i64`, when it fits.
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.digits.len() > 2 {
+            return None;
+        }
+        let magnitude = self
+            .digits
+            .iter()
+            .rev()
+            .fold(0u64, |magnitude, digit| magnitude << 32 | *digit as u64);
+        if self.negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+
+    /// The nearest `f64`, infinite when `self` is out of its range.
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .digits
+            .iter()
+            .rev()
+            .fold(0f64, |magnitude, digit| magnitude * 4294967296.0 + *digit as f64);
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    /// Quotient and remainder of `self` by `divisor`, truncating
+    /// towards zero like `i64` division: the remainder has the sign
+    /// of `self`.
+    ///
+    /// # Panics
+    ///
+    /// When `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigInteger) -> (BigInteger, BigInteger) {
+        if divisor.is_zero() {
+            panic!("attempt to divide {} by zero", self);
+        }
+        let (quotient, remainder) = div_rem_digits(&self.digits, &divisor.digits);
+        (
+            BigInteger::from_digits(self.negative != divisor.negative, quotient),
+            BigInteger::from_digits(self.negative, remainder),
+        )
+    }
+
+    /// Greatest common divisor of `self` and `other`, never negative.
+    pub fn gcd(&self, other: &BigInteger) -> BigInteger {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let (_, remainder) = a.div_rem(&b);
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+}
+
+impl From<i64> for BigInteger {
+    fn from(value: i64) -> BigInteger {
+        let magnitude = value.unsigned_abs();
+        BigInteger::from_digits(
+            value < 0,
+            vec![magnitude as u32, (magnitude >> 32) as u32],
+        )
+    }
+}
+
+impl From<u32> for BigInteger {
+    fn from(value: u32) -> BigInteger {
+        BigInteger::from(value as i64)
+    }
+}
+
+impl BigInteger {
+    /// Reads digits in base `radix`, from 2 to 36, with an optional
+    /// sign.
+    pub fn from_str_radix(literal: &str, radix: u32) -> Result<BigInteger, String> {
+        let (negative, digits) = match literal.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, literal.strip_prefix('+').unwrap_or(literal)),
+        };
+        if digits.is_empty() {
+            return Err(format!("invalid integer literal {:#?}", literal));
+        }
+        let mut magnitude = Vec::<u32>::new();
+        for c in digits.chars() {
+            let Some(digit) = c.to_digit(radix) else {
+                return Err(format!("invalid digit in integer literal {:#?}", literal));
+            };
+            let mut carry = digit as u64;
+            for place in magnitude.iter_mut() {
+                let product = *place as u64 * radix as u64 + carry;
+                *place = product as u32;
+                carry = product >> 32;
+            }
+            if carry > 0 {
+                magnitude.push(carry as u32);
+            }
+        }
+        Ok(BigInteger::from_digits(negative, magnitude))
+    }
+}
+
+impl FromStr for BigInteger {
+    type Err = String;
+
+    /// Reads decimal digits with an optional sign.
+    fn from_str(literal: &str) -> Result<BigInteger, String> {
+        BigInteger::from_str_radix(literal, 10)
+    }
+}
+
+impl Neg for BigInteger {
+    type Output = BigInteger;
+
+    fn neg(self) -> BigInteger {
+        BigInteger::from_digits(!self.negative, self.digits)
+    }
+}
+
+impl Add for BigInteger {
+    type Output = BigInteger;
+
+    fn add(self, other: BigInteger) -> BigInteger {
+        if self.negative == other.negative {
+            return BigInteger::from_digits(
+                self.negative,
+                add_digits(&self.digits, &other.digits),
+            );
+        }
+        match compare_digits(&self.digits, &other.digits) {
+            Ordering::Less => BigInteger::from_digits(
+                other.negative,
+                sub_digits(&other.digits, &self.digits),
+            ),
+            _ => BigInteger::from_digits(
+                self.negative,
+                sub_digits(&self.digits, &other.digits),
+            ),
+        }
+    }
+}
+
+impl Sub for BigInteger {
+    type Output = BigInteger;
+
+    fn sub(self, other: BigInteger) -> BigInteger {
+        self + -other
+    }
+}
+
+impl Mul for BigInteger {
+    type Output = BigInteger;
+
+    fn mul(self, other: BigInteger) -> BigInteger {
+        BigInteger::from_digits(
+            self.negative != other.negative,
+            mul_digits(&self.digits, &other.digits),
+        )
+    }
+}
+
+impl PartialOrd for BigInteger {
+    fn partial_cmp(&self, other: &BigInteger) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInteger {
+    fn cmp(&self, other: &BigInteger) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_digits(&self.digits, &other.digits),
+            (true, true) => compare_digits(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl Display for BigInteger {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // groups of nine decimal digits, least significant first
+        let mut groups = Vec::<u32>::new();
+        let mut magnitude = self.digits.clone();
+        while !magnitude.is_empty() {
+            let (quotient, remainder) = div_rem_digit(&magnitude, 1_000_000_000);
+            groups.push(remainder);
+            magnitude = quotient;
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        let mut groups = groups.iter().rev();
+        write!(f, "{}", groups.next().expect("digit"))?;
+        for group in groups {
+            write!(f, "{:09}", group)?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for BigInteger {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+fn trimmed(mut digits: Vec<u32>) -> Vec<u32> {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+fn compare_digits(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for index in 0..a.len().max(b.len()) {
+        let total = *a.get(index).unwrap_or(&0) as u64
+            + *b.get(index).unwrap_or(&0) as u64
+            + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    sum.push(carry as u32);
+    trimmed(sum)
+}
+
+/// `a - b`, where `a` is at least `b`.
+fn sub_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (index, digit) in a.iter().enumerate() {
+        let mut total = *digit as i64 - *b.get(index).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if total < 0 {
+            total += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(total as u32);
+    }
+    trimmed(difference)
+}
+
+fn mul_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, y) in b.iter().enumerate() {
+            let total = *x as u64 * *y as u64 + product[i + j] as u64 + carry;
+            product[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    trimmed(product)
+}
+
+fn div_rem_digit(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = 0u64;
+    for (index, digit) in a.iter().enumerate().rev() {
+        let total = remainder << 32 | *digit as u64;
+        quotient[index] = (total / divisor as u64) as u32;
+        remainder = total % divisor as u64;
+    }
+    (trimmed(quotient), remainder as u32)
+}
+
+/// Long division of `a` by a non-zero `b`, one bit at a time.
+fn div_rem_digits(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if let [divisor] = b {
+        let (quotient, remainder) = div_rem_digit(a, *divisor);
+        return (quotient, trimmed(vec![remainder]));
+    }
+    if compare_digits(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = Vec::<u32>::new();
+    for bit in (0..a.len() * 32).rev() {
+        // remainder = remainder * 2 + the next bit of a
+        let mut carry = (a[bit / 32] >> (bit % 32)) & 1;
+        for digit in remainder.iter_mut() {
+            let shifted = *digit >> 31;
+            *digit = *digit << 1 | carry;
+            carry = shifted;
+        }
+        if carry > 0 {
+            remainder.push(carry);
+        }
+        if compare_digits(&remainder, b) != Ordering::Less {
+            remainder = sub_digits(&remainder, b);
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    (trimmed(quotient), remainder)
+}
@@ -0,0 +1,368 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{BigInteger, Rational, Value};
+
+/// A number of any type, for arithmetic across types.
+///
+/// The types form a tower, from [`Number::UnsignedInteger`] up to
+/// [`Number::Float`]. Operands of different types are promoted to the
+/// higher one before an operation, and a result that does not fit
+/// its type moves up: integers that overflow `i64` become
+/// [`Number::BigInteger`], and integers that do not divide evenly
+/// make a [`Number::Rational`]. Big integers and rationals are moved
+/// back down when their value fits in an `i64`, to the type the
+/// reader gives the same integer.
+///
+/// The `checked_` methods return an [`ArithmeticError`] where the
+/// operators panic.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Number {
+    UnsignedInteger(u32),
+    Integer(i64),
+    BigInteger(BigInteger),
+    Rational(Rational),
+    Float(f64),
+}
+
+/// Why an arithmetic operation on [`Number`]s has no result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArithmeticError {
+    DivisionByZero,
+    /// A float operation on numbers gave NaN, as `(- 1.0e+INF
+    /// 1.0e+INF)` does.
+    NotANumber,
+    /// A float operation on finite numbers gave an infinite result, or
+    /// an integer grew beyond [`INTEGER_WIDTH`] bits.
+    Overflow,
+}
+
+impl ArithmeticError {
+    /// The elisp error symbol, `arith-error` or `overflow-error`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArithmeticError::DivisionByZero | ArithmeticError::NotANumber => "arith-error",
+            ArithmeticError::Overflow => "overflow-error",
+        }
+    }
+}
+
+impl Display for ArithmeticError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ArithmeticError::DivisionByZero => write!(f, "division by zero"),
+            ArithmeticError::NotANumber => write!(f, "result is not a number"),
+            ArithmeticError::Overflow => write!(f, "result overflows"),
+        }
+    }
+}
+
+/// How many bits the integers and the parts of rationals resulting
+/// from arithmetic can have, like elisp `integer-width`.
+pub const INTEGER_WIDTH: usize = 65536;
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Level {
+    UnsignedInteger,
+    Integer,
+    BigInteger,
+    Rational,
+    Float,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Number {
+    /// The number held by `value`, or `None` when it is not a number.
+    pub fn from_value(value: &Value) -> Option<Number> {
+        match value {
+            Value::UnsignedInteger(integer) => Some(Number::UnsignedInteger(**integer)),
+            Value::Integer(integer) => Some(Number::Integer(**integer)),
+            Value::BigInteger(integer) => Some(Number::BigInteger(integer.clone())),
+            Value::Rational(rational) => Some(Number::Rational(rational.clone())),
+            Value::Float(float) => Some(Number::Float(**float)),
+            _ => None,
+        }
+    }
+
+    /// An integer of any size in its smallest representation.
+    pub fn from_big_integer(integer: BigInteger) -> Number {
+        match integer.to_i64() {
+            Some(integer) => Number::from_i64(integer),
+            None => Number::BigInteger(integer),
+        }
+    }
+
+    /// A rational, or an integer when its denominator is one.
+    pub fn from_rational(rational: Rational) -> Number {
+        if rational.is_integer() {
+            Number::from_big_integer(rational.numerator().clone())
+        } else {
+            Number::Rational(rational)
+        }
+    }
+
+    /// `integer` as an unsigned integer when it is one.
+    fn from_i64(integer: i64) -> Number {
+        match u32::try_from(integer) {
+            Ok(unsigned) => Number::UnsignedInteger(unsigned),
+            Err(_) => Number::Integer(integer),
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Number::UnsignedInteger(integer) => *integer as f64,
+            Number::Integer(integer) => *integer as f64,
+            Number::BigInteger(integer) => integer.to_f64(),
+            Number::Rational(rational) => rational.to_f64(),
+            Number::Float(float) => *float,
+        }
+    }
+
+    fn level(&self) -> Level {
+        match self {
+            Number::UnsignedInteger(_) => Level::UnsignedInteger,
+            Number::Integer(_) => Level::Integer,
+            Number::BigInteger(_) => Level::BigInteger,
+            Number::Rational(_) => Level::Rational,
+            Number::Float(_) => Level::Float,
+        }
+    }
+
+    /// `self` as an `i64`, for numbers at the integer levels.
+    fn to_i64(&self) -> i64 {
+        match self {
+            Number::UnsignedInteger(integer) => *integer as i64,
+            Number::Integer(integer) => *integer,
+            number => unreachable!("{:#?} is not a small integer", number),
+        }
+    }
+
+    /// `self` as a big integer, for numbers at the integer levels.
+    fn to_big_integer(&self) -> BigInteger {
+        match self {
+            Number::BigInteger(integer) => integer.clone(),
+            number => BigInteger::from(number.to_i64()),
+        }
+    }
+
+    fn to_rational(&self) -> Rational {
+        match self {
+            Number::Rational(rational) => rational.clone(),
+            number => Rational::from(number.to_big_integer()),
+        }
+    }
+
+    /// Applies `operator` at the higher level of `self` and `other`,
+    /// moving up to a level that can hold the result.
+    fn apply(self, other: Number, operator: Operator) -> Result<Number, ArithmeticError> {
+        if operator == Operator::Div && other.is_zero() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let level = if self.level() > other.level() {
+            self.level()
+        } else {
+            other.level()
+        };
+        if level == Level::Float {
+            let (a, b) = (self.to_f64(), other.to_f64());
+            let result = operator.floats(a, b);
+            if result.is_nan() && !a.is_nan() && !b.is_nan() {
+                return Err(ArithmeticError::NotANumber);
+            }
+            if result.is_infinite() && a.is_finite() && b.is_finite() {
+                return Err(ArithmeticError::Overflow);
+            }
+            return Ok(Number::Float(result));
+        }
+        if level <= Level::Integer {
+            if let Some(result) = operator.integers(self.to_i64(), other.to_i64()) {
+                return Ok(match level {
+                    Level::UnsignedInteger => Number::from_i64(result),
+                    _ => Number::Integer(result),
+                });
+            }
+        }
+        let result = if level <= Level::BigInteger && operator != Operator::Div {
+            Number::from_big_integer(
+                operator.big_integers(self.to_big_integer(), other.to_big_integer()),
+            )
+        } else {
+            Number::from_rational(operator.rationals(self.to_rational(), other.to_rational()))
+        };
+        let bits = match &result {
+            Number::BigInteger(integer) => integer.bits(),
+            Number::Rational(rational) =>
+                rational.numerator().bits().max(rational.denominator().bits()),
+            _ => 0,
+        };
+        if bits > INTEGER_WIDTH {
+            return Err(ArithmeticError::Overflow);
+        }
+        Ok(result)
+    }
+
+    pub fn checked_add(self, other: Number) -> Result<Number, ArithmeticError> {
+        self.apply(other, Operator::Add)
+    }
+
+    pub fn checked_sub(self, other: Number) -> Result<Number, ArithmeticError> {
+        self.apply(other, Operator::Sub)
+    }
+
+    pub fn checked_mul(self, other: Number) -> Result<Number, ArithmeticError> {
+        self.apply(other, Operator::Mul)
+    }
+
+    /// Divides exactly: integers that do not divide evenly make a
+    /// rational.
+    pub fn checked_div(self, other: Number) -> Result<Number, ArithmeticError> {
+        self.apply(other, Operator::Div)
+    }
+
+    /// Compares the values of `self` and `other` exactly, whatever
+    /// their types, like elisp `<` and `=`. `None` when either is NaN.
+    pub fn compare(&self, other: &Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Float(a), Number::Float(b)) => a.partial_cmp(b),
+            (Number::Float(a), b) => compare_float(*a, b),
+            (a, Number::Float(b)) => compare_float(*b, a).map(Ordering::reverse),
+            (a, b) if a.level() <= Level::Integer && b.level() <= Level::Integer =>
+                Some(a.to_i64().cmp(&b.to_i64())),
+            (a, b) => Some(a.to_rational().cmp(&b.to_rational())),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Number::UnsignedInteger(integer) => *integer == 0,
+            Number::Integer(integer) => *integer == 0,
+            Number::BigInteger(integer) => integer.is_zero(),
+            Number::Rational(_) => false,
+            Number::Float(float) => *float == 0.0,
+        }
+    }
+}
+
+/// Compares `float` with a number that is not a float.
+fn compare_float(float: f64, number: &Number) -> Option<Ordering> {
+    if float.is_nan() {
+        None
+    } else if float.is_infinite() {
+        Some(if float > 0.0 { Ordering::Greater } else { Ordering::Less })
+    } else {
+        Rational::from_f64(float).map(|float| float.cmp(&number.to_rational()))
+    }
+}
+
+impl Operator {
+    /// The result of an operation on integers, or `None` when it
+    /// overflows or is not an integer.
+    fn integers(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Operator::Add => a.checked_add(b),
+            Operator::Sub => a.checked_sub(b),
+            Operator::Mul => a.checked_mul(b),
+            Operator::Div => match a.checked_rem(b) {
+                Some(0) => a.checked_div(b),
+                _ => None,
+            },
+        }
+    }
+
+    fn big_integers(self, a: BigInteger, b: BigInteger) -> BigInteger {
+        match self {
+            Operator::Add => a + b,
+            Operator::Sub => a - b,
+            Operator::Mul => a * b,
+            Operator::Div => unreachable!("big integers are divided as rationals"),
+        }
+    }
+
+    fn rationals(self, a: Rational, b: Rational) -> Rational {
+        match self {
+            Operator::Add => a + b,
+            Operator::Sub => a - b,
+            Operator::Mul => a * b,
+            Operator::Div => a / b,
+        }
+    }
+
+    fn floats(self, a: f64, b: f64) -> f64 {
+        match self {
+            Operator::Add => a + b,
+            Operator::Sub => a - b,
+            Operator::Mul => a * b,
+            Operator::Div => a / b,
+        }
+    }
+}
+
+impl Add for Number {
+    type Output = Number;
+
+    fn add(self, other: Number) -> Number {
+        self.checked_add(other).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl Sub for Number {
+    type Output = Number;
+
+    fn sub(self, other: Number) -> Number {
+        self.checked_sub(other).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl Mul for Number {
+    type Output = Number;
+
+    fn mul(self, other: Number) -> Number {
+        self.checked_mul(other).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl Div for Number {
+    type Output = Number;
+
+    fn div(self, other: Number) -> Number {
+        self.checked_div(other).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl Neg for Number {
+    type Output = Number;
+
+    fn neg(self) -> Number {
+        match self {
+            Number::UnsignedInteger(integer) => Number::from_i64(-(integer as i64)),
+            Number::Integer(integer) => match integer.checked_neg() {
+                Some(integer) => Number::Integer(integer),
+                None => Number::BigInteger(-BigInteger::from(integer)),
+            },
+            Number::BigInteger(integer) => Number::from_big_integer(-integer),
+            Number::Rational(rational) => Number::Rational(-rational),
+            Number::Float(float) => Number::Float(-float),
+        }
+    }
+}
+
+impl<'c> From<Number> for Value<'c> {
+    fn from(number: Number) -> Value<'c> {
+        match number {
+            Number::UnsignedInteger(integer) => Value::unsigned_integer(integer),
+            Number::Integer(integer) => Value::integer(integer),
+            Number::BigInteger(integer) => Value::BigInteger(integer),
+            Number::Rational(rational) => Value::Rational(rational),
+            Number::Float(float) => Value::float(float),
+        }
+    }
+}
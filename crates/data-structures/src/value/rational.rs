@@ -0,0 +1,168 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::BigInteger;
+
+/// Exact quotient of two integers, the result of dividing integers
+/// that do not divide evenly, such as `(/ 1 3)`.
+///
+/// It is always in lowest terms with a positive denominator, so that
+/// equal rationals compare and hash the same.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: BigInteger,
+    denominator: BigInteger,
+}
+
+impl Rational {
+    /// `numerator / denominator` in lowest terms.
+    ///
+    /// # Panics
+    ///
+    /// When `denominator` is zero.
+    pub fn new(numerator: BigInteger, denominator: BigInteger) -> Rational {
+        if denominator.is_zero() {
+            panic!("attempt to divide {} by zero", numerator);
+        }
+        let gcd = numerator.gcd(&denominator);
+        let (mut numerator, _) = numerator.div_rem(&gcd);
+        let (mut denominator, _) = denominator.div_rem(&gcd);
+        if denominator.is_negative() {
+            numerator = -numerator;
+            denominator = -denominator;
+        }
+        Rational {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// The exact value of `float`, or `None` when it is infinite or
+    /// NaN.
+    pub fn from_f64(float: f64) -> Option<Rational> {
+        if !float.is_finite() {
+            return None;
+        }
+        let bits = float.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i64;
+        let fraction = (bits & ((1 << 52) - 1)) as i64;
+        // float is mantissa * 2^exponent
+        let (mantissa, exponent) = match exponent {
+            0 => (fraction, -1074),
+            _ => (fraction | 1 << 52, exponent - 1075),
+        };
+        let mantissa = BigInteger::from(if float < 0.0 { -mantissa } else { mantissa });
+        let power = BigInteger::power_of_two(exponent.unsigned_abs() as usize);
+        Some(if exponent < 0 {
+            Rational::new(mantissa, power)
+        } else {
+            Rational::from(mantissa * power)
+        })
+    }
+
+    pub fn numerator(&self) -> &BigInteger {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &BigInteger {
+        &self.denominator
+    }
+
+    /// `true` when the denominator is one.
+    pub fn is_integer(&self) -> bool {
+        self.denominator == BigInteger::one()
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator.to_f64() / self.denominator.to_f64()
+    }
+}
+
+impl From<BigInteger> for Rational {
+    fn from(integer: BigInteger) -> Rational {
+        Rational {
+            numerator: integer,
+            denominator: BigInteger::one(),
+        }
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        Rational::new(
+            self.numerator * other.denominator.clone()
+                + other.numerator * self.denominator.clone(),
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        Rational::new(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Rational) -> Rational {
+        Rational::new(
+            self.numerator * other.denominator,
+            self.denominator * other.numerator,
+        )
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        // denominators are positive
+        (self.numerator.clone() * other.denominator.clone())
+            .cmp(&(other.numerator.clone() * self.denominator.clone()))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+impl Debug for Rational {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
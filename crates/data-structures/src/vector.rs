@@ -0,0 +1,123 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::rc::Rc;
+
+use crate::{circular, printer, Value};
+
+/// Fixed-size array of values backed by contiguous storage.
+///
+/// Clones share the same storage, so [`Vector::set`] is visible
+/// through every copy, like `aset` on an elisp vector. Equality,
+/// ordering and hashing compare the elements, and terminate on a
+/// vector that contains itself.
+#[derive(Clone)]
+pub struct Vector<'c> {
+    items: Rc<RefCell<Vec<Value<'c>>>>,
+}
+
+impl<'c> Vector<'c> {
+    pub fn new(items: Vec<Value<'c>>) -> Vector<'c> {
+        Vector {
+            items: Rc::new(RefCell::new(items)),
+        }
+    }
+
+    /// `O(1)`
+    pub fn len(&self) -> usize {
+        self.items.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.borrow().is_empty()
+    }
+
+    /// `O(1)`
+    pub fn get(&self, index: usize) -> Option<Value<'c>> {
+        self.items.borrow().get(index).cloned()
+    }
+
+    /// Replaces the element at `index` in place and returns the
+    /// previous one, or `None` when `index` is out of range.
+    ///
+    /// `O(1)`
+    pub fn set(&self, index: usize, value: Value<'c>) -> Option<Value<'c>> {
+        self.items
+            .borrow_mut()
+            .get_mut(index)
+            .map(|item| std::mem::replace(item, value))
+    }
+
+    pub fn values(&self) -> Vec<Value<'c>> {
+        self.items.borrow().clone()
+    }
+
+    /// Address of the storage, identifying this vector while it is
+    /// alive.
+    pub(crate) fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(&self.items).cast()
+    }
+
+    /// `true` when `self` and `other` share the same storage.
+    pub fn ptr_eq(&self, other: &Vector<'c>) -> bool {
+        Rc::ptr_eq(&self.items, &other.items)
+    }
+}
+
+impl<'c> FromIterator<Value<'c>> for Vector<'c> {
+    fn from_iter<I: IntoIterator<Item = Value<'c>>>(iter: I) -> Vector<'c> {
+        Vector::new(iter.into_iter().collect())
+    }
+}
+
+impl<'c> Default for Vector<'c> {
+    fn default() -> Vector<'c> {
+        Vector::new(Vec::new())
+    }
+}
+
+impl<'c> PartialEq for Vector<'c> {
+    fn eq(&self, other: &Vector<'c>) -> bool {
+        circular::vectors_equal(self, other)
+    }
+}
+impl<'c> Eq for Vector<'c> {}
+
+impl<'c> PartialOrd for Vector<'c> {
+    fn partial_cmp(&self, other: &Vector<'c>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<'c> Ord for Vector<'c> {
+    fn cmp(&self, other: &Vector<'c>) -> Ordering {
+        circular::cmp_vectors(self, other)
+    }
+}
+
+impl<'c> Hash for Vector<'c> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        circular::hash_vector(self, state);
+    }
+}
+
+impl Display for Vector<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", printer::display(&Value::Vector(self.clone())))
+    }
+}
+impl Debug for Vector<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}]",
+            self.items
+                .borrow()
+                .iter()
+                .map(|value| format!("{:#?}", value))
+                .collect::<Vec<String>>()
+                .join(" ")
+        )
+    }
+}
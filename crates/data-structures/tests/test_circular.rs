@@ -0,0 +1,153 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use k9::assert_equal;
+use minilisp_data_structures::{cdr, list, prin1, setcar, setcdr, Cell, Printer, Value};
+
+fn hash(value: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// `(a b c)` whose last cdr is its first cell.
+fn circular() -> Value<'static> {
+    let value = list([Value::symbol("a"), Value::symbol("b"), Value::symbol("c")]);
+    setcdr(&cdr(&cdr(&value)), value.clone());
+    value
+}
+
+/// The cells of `value` itself rather than a copy of them.
+fn cells<'c>(value: &Value<'c>) -> Cell<'c> {
+    match value {
+        Value::List(cell) => cell.clone(),
+        value => panic!("{} is not a list", value),
+    }
+}
+
+const CIRCLE: Printer = Printer {
+    readable: true,
+    circle: true,
+};
+
+#[test]
+fn test_printing_a_circular_cdr() {
+    let value = circular();
+    assert_equal!(prin1(&value), "(a b c . #0)");
+    assert_equal!(value.to_string(), "(a b c . #0)");
+    assert_equal!(CIRCLE.print(&value), "#1=(a b c . #1#)");
+
+    let tail = cdr(&value);
+    assert_equal!(CIRCLE.print(&tail), "#1=(b c a . #1#)");
+}
+
+#[test]
+fn test_printing_a_list_containing_itself() {
+    let value = list([Value::symbol("a"), Value::Nil]);
+    setcar(&cdr(&value), value.clone());
+    assert_equal!(prin1(&value), "(a #0)");
+    assert_equal!(CIRCLE.print(&value), "#1=(a #1#)");
+
+    let vector = Value::vector([Value::T]);
+    if let Value::Vector(items) = &vector {
+        items.set(0, vector.clone());
+    }
+    assert_equal!(prin1(&vector), "[#0]");
+    assert_equal!(CIRCLE.print(&vector), "#1=[#1#]");
+}
+
+#[test]
+fn test_print_circle_labels_shared_structure() {
+    let shared = list([Value::symbol("x")]);
+    let value = list([shared.clone(), shared.clone(), Value::vector([shared])]);
+    assert_equal!(prin1(&value), "((x) (x) [(x)])");
+    assert_equal!(CIRCLE.print(&value), "(#1=(x) #1# [#1#])");
+
+    let tail = list([Value::symbol("b"), Value::symbol("c")]);
+    setcdr(&cdr(&tail), tail.clone());
+    let value = Value::from_iter([Value::symbol("a"), Value::Nil]);
+    setcdr(&value, tail);
+    assert_equal!(CIRCLE.print(&value), "(a . #1=(b c . #1#))");
+}
+
+#[test]
+fn test_equal_and_hash_terminate_on_circular_lists() {
+    let (a, b) = (circular(), circular());
+    assert_equal!(a == b, true);
+    assert_equal!(hash(&a), hash(&b));
+
+    // the same cycle, unfolded once more
+    let unfolded = list([
+        Value::symbol("a"),
+        Value::symbol("b"),
+        Value::symbol("c"),
+        Value::symbol("a"),
+        Value::symbol("b"),
+        Value::symbol("c"),
+    ]);
+    setcdr(&cdr(&cdr(&cdr(&cdr(&cdr(&unfolded))))), unfolded.clone());
+    assert_equal!(a == unfolded, true);
+    assert_equal!(hash(&a), hash(&unfolded));
+
+    let other = circular();
+    setcar(&cdr(&other), Value::symbol("x"));
+    assert_equal!(a == other, false);
+    assert_equal!(a == list([Value::symbol("a"), Value::symbol("b"), Value::symbol("c")]), false);
+}
+
+#[test]
+fn test_comparing_vectors_containing_themselves() {
+    let a = Value::vector([Value::T]);
+    if let Value::Vector(items) = &a {
+        items.set(0, a.clone());
+    }
+    let b = Value::vector([Value::T]);
+    if let Value::Vector(items) = &b {
+        items.set(0, b.clone());
+    }
+    assert_equal!(a == b, true);
+    assert_equal!(a.cmp(&b), std::cmp::Ordering::Equal);
+    assert_equal!(a.cmp(&Value::vector([a.clone(), Value::T])), std::cmp::Ordering::Less);
+}
+
+#[test]
+fn test_walking_a_circular_list_visits_each_cell_once() {
+    let value = circular();
+    let cell = cells(&value);
+    assert_equal!(cell.len(), 3);
+    assert_equal!(
+        cell.values(),
+        vec![Value::symbol("a"), Value::symbol("b"), Value::symbol("c")]
+    );
+    assert_equal!(cell.last().head(), Some(Value::symbol("c")));
+    assert_equal!(cell.dotted_tail(), None);
+    assert_equal!(cell.is_circular(), true);
+    assert_equal!(cell.is_proper(), false);
+
+    // a cycle that starts after the first cell
+    let value = Value::from_iter([Value::symbol("x"), Value::Nil]);
+    setcdr(&value, circular());
+    assert_equal!(cells(&value).len(), 4);
+    assert_equal!(cells(&value).last().head(), Some(Value::symbol("c")));
+
+    let proper = cells(&list([Value::symbol("a"), Value::symbol("b")]));
+    assert_equal!(proper.is_circular(), false);
+    assert_equal!(proper.is_proper(), true);
+}
+
+#[test]
+fn test_comparing_circular_lists() {
+    let (a, b) = (circular(), circular());
+    assert_equal!(a.cmp(&b), Ordering::Equal);
+
+    let other = circular();
+    setcar(&cdr(&other), Value::symbol("x"));
+    assert_equal!(a.cmp(&other), Ordering::Less);
+
+    let value = list([Value::symbol("a"), Value::Nil]);
+    setcar(&cdr(&value), value.clone());
+    let same = list([Value::symbol("a"), Value::Nil]);
+    setcar(&cdr(&same), same.clone());
+    assert_equal!(value.cmp(&same), Ordering::Equal);
+}
@@ -1,6 +1,6 @@
 #![allow(unused)]
 use k9::assert_equal;
-use minilisp_data_structures::{append, car, cdr, cons, list, Cell, Value, assert_display_equal, assert_debug_equal};
+use minilisp_data_structures::{append, car, cdr, cons, consp, delq, list, listp, nconc, nreverse, pair, plist_get, plist_put, proper_list_p, setcar, setcdr, Cell, Value, assert_display_equal, assert_debug_equal};
 
 #[test]
 fn test_cons() {
@@ -80,3 +80,132 @@ fn test_append() {
         ]
     );
 }
+
+#[test]
+fn test_pair_of_atoms_is_improper() {
+    let value = pair(Value::symbol("a"), Value::symbol("b"));
+    assert_display_equal!(value, "(a . b)");
+    assert_equal!(car(&value), Value::symbol("a"));
+    assert_equal!(cdr(&value), Value::symbol("b"));
+    assert_equal!(consp(&value), true);
+    assert_equal!(listp(&value), true);
+    assert_equal!(proper_list_p(&value), None);
+}
+
+#[test]
+fn test_pair_with_list_cdr_is_proper() {
+    let value = pair(
+        Value::symbol("a"),
+        list([Value::symbol("b"), Value::symbol("c")]),
+    );
+    assert_display_equal!(value, "(a b c)");
+    assert_equal!(proper_list_p(&value), Some(3));
+
+    let value = pair(Value::symbol("a"), Value::nil());
+    assert_display_equal!(value, "(a)");
+    assert_equal!(proper_list_p(&value), Some(1));
+}
+
+#[test]
+fn test_improper_list_with_several_elements() {
+    let value = pair(
+        Value::symbol("a"),
+        pair(Value::symbol("b"), Value::symbol("c")),
+    );
+    assert_display_equal!(value, "(a b . c)");
+    assert_display_equal!(cdr(&value), "(b . c)");
+    assert_display_equal!(cdr(&cdr(&value)), "c");
+    assert_equal!(value.len(), 2);
+    assert_equal!(
+        value,
+        pair(Value::symbol("a"), pair(Value::symbol("b"), Value::symbol("c")))
+    );
+    assert_equal!(
+        value == pair(Value::symbol("a"), pair(Value::symbol("b"), Value::symbol("d"))),
+        false
+    );
+}
+
+#[test]
+fn test_predicates_on_atoms_and_nil() {
+    assert_equal!(consp(&Value::nil()), false);
+    assert_equal!(listp(&Value::nil()), true);
+    assert_equal!(proper_list_p(&Value::nil()), Some(0));
+    assert_equal!(consp(&Value::symbol("a")), false);
+    assert_equal!(listp(&Value::symbol("a")), false);
+    assert_equal!(proper_list_p(&Value::symbol("a")), None);
+}
+
+#[test]
+fn test_setcar_and_setcdr_mutate_shared_cells() {
+    let a = list([Value::symbol("a"), Value::symbol("b"), Value::symbol("c")]);
+    let b = a.clone();
+    let tail = cdr(&a);
+    assert_equal!(setcar(&b, Value::symbol("x")), Some(Value::symbol("x")));
+    assert_equal!(setcar(&tail, Value::symbol("y")), Some(Value::symbol("y")));
+    assert_equal!(a.to_string(), "(x y c)");
+    assert_equal!(setcdr(&tail, Value::symbol("z")), Some(Value::symbol("z")));
+    assert_equal!(b.to_string(), "(x y . z)");
+    assert_equal!(setcar(&Value::Nil, Value::T), None);
+    assert_equal!(setcdr(&Value::T, Value::T), None);
+}
+
+#[test]
+fn test_nconc_nreverse_and_delq() {
+    let a = list([Value::symbol("a"), Value::symbol("b")]);
+    let b = list([Value::symbol("c")]);
+    let joined = nconc([Value::Nil, a.clone(), b.clone()]).expect("lists");
+    assert_equal!(joined.to_string(), "(a b c)");
+    assert_equal!(a.to_string(), "(a b c)");
+    assert_equal!(nconc([Value::T, a.clone()]), None);
+
+    let reversed = nreverse(&a).expect("proper list");
+    assert_equal!(reversed.to_string(), "(c b a)");
+    assert_equal!(a.to_string(), "(a)");
+    assert_equal!(b.to_string(), "(c b a)");
+
+    let deleted = delq(&Value::symbol("c"), &reversed).expect("list");
+    assert_equal!(deleted.to_string(), "(b a)");
+    assert_equal!(delq(&Value::symbol("c"), &Value::T), None);
+}
+
+#[test]
+fn test_dropping_a_long_list() {
+    let value = Value::from_iter((0..1_000_000u32).map(Value::unsigned_integer));
+    assert_equal!(value.len(), 1_000_000);
+    drop(value);
+}
+
+#[test]
+fn test_cons_and_cdr_share_cells() {
+    let tail = list([Value::symbol("b"), Value::symbol("c")]);
+    let consed = pair(Value::symbol("a"), tail.clone());
+    assert_equal!(consed.to_string(), "(a b c)");
+    match (&cdr(&consed), &tail) {
+        (Value::List(rest), Value::List(tail)) => {
+            assert_equal!(rest.ptr_eq(tail), true);
+        },
+        _ => panic!("expected lists"),
+    }
+    setcar(&tail, Value::symbol("x"));
+    assert_equal!(consed.to_string(), "(a x c)");
+
+    let cell = cons("head", &Cell::from("tail"));
+    assert_equal!(Cell::cons(Value::T, Value::list(cell)).to_string(), "t head tail");
+}
+
+#[test]
+fn test_plist_get_and_plist_put() {
+    let plist = plist_put(&Value::Nil, Value::symbol("a"), Value::unsigned_integer(1u32));
+    assert_equal!(plist.to_string(), "(a 1)");
+    let same = plist_put(&plist, Value::symbol("b"), Value::unsigned_integer(2u32));
+    assert_equal!(plist.to_string(), "(a 1 b 2)");
+    plist_put(&same, Value::quoted_symbol("a"), Value::unsigned_integer(3u32));
+    assert_equal!(plist.to_string(), "(a 3 b 2)");
+    assert_equal!(
+        plist_get(&plist, &Value::symbol("b")),
+        Some(Value::unsigned_integer(2u32))
+    );
+    assert_equal!(plist_get(&plist, &Value::symbol("c")), None);
+    assert_equal!(plist_get(&Value::Nil, &Value::symbol("a")), None);
+}
@@ -0,0 +1,42 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+
+use k9::assert_equal;
+use minilisp_data_structures::hash_table::Test;
+use minilisp_data_structures::{list, Value};
+
+fn hash_equal(value: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash_equal(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_eq_eql_and_equal() {
+    let a = list([Value::symbol("a"), Value::unsigned_integer(1u32)]);
+    let b = list([Value::symbol("a"), Value::integer(1i64)]);
+    assert_equal!(a.is_eq(&a.clone()), true);
+    assert_equal!(a.is_eq(&b), false);
+    assert_equal!(a.is_equal(&b), true);
+    assert_equal!(a == b, false);
+    assert_equal!(hash_equal(&a), hash_equal(&b));
+
+    assert_equal!(Value::symbol("a").is_eq(&Value::quoted_symbol("a")), true);
+    assert_equal!(Value::Nil.is_eq(&Value::EmptyQuotedList), true);
+    assert_equal!(Value::float(1.0).is_eql(&Value::float(1.0)), true);
+    assert_equal!(Value::float(1.0).is_eql(&Value::unsigned_integer(1u32)), false);
+    assert_equal!(Value::float(f64::NAN).is_eql(&Value::float(f64::NAN)), true);
+
+    let string = Value::string("s");
+    assert_equal!(string.is_eq(&string.clone()), true);
+    assert_equal!(string.is_eq(&Value::string("s")), false);
+    assert_equal!(string.is_equal(&Value::string("s")), true);
+}
+
+#[test]
+fn test_hash_table_tests() {
+    let one = Value::unsigned_integer(1u32);
+    assert_equal!(Test::Eql.matches(&one, &Value::integer(1i64)), true);
+    assert_equal!(Test::Eq.matches(&Value::string("s"), &Value::string("s")), false);
+    assert_equal!(Test::Equal.matches(&Value::string("s"), &Value::string("s")), true);
+}
@@ -0,0 +1,68 @@
+use k9::assert_equal;
+use minilisp_data_structures::hash_table::Test;
+use minilisp_data_structures::{list, prin1, HashTable, Value};
+
+#[test]
+fn test_insert_get_and_remove() {
+    let table = HashTable::new(Test::Eql);
+    assert_equal!(table.insert(Value::symbol("a"), Value::from(1u64)), None);
+    assert_equal!(table.insert(Value::symbol("b"), Value::from(2u64)), None);
+    assert_equal!(
+        table.insert(Value::quoted_symbol("a"), Value::from(3u64)),
+        Some(Value::from(1u64))
+    );
+    assert_equal!(table.get(&Value::symbol("a")), Some(Value::from(3u64)));
+    assert_equal!(table.len(), 2);
+    assert_equal!(table.keys(), vec![Value::symbol("a"), Value::symbol("b")]);
+
+    assert_equal!(table.remove(&Value::symbol("a")), Some(Value::from(3u64)));
+    assert_equal!(table.remove(&Value::symbol("a")), None);
+    assert_equal!(table.get(&Value::symbol("a")), None);
+    assert_equal!(table.keys(), vec![Value::symbol("b")]);
+}
+
+#[test]
+fn test_keys_stay_in_insertion_order_across_removals() {
+    let table = HashTable::new(Test::Eql);
+    for n in 0..100u64 {
+        table.insert(Value::from(n), Value::T);
+    }
+    for n in 0..90u64 {
+        table.remove(&Value::from(n));
+    }
+    table.insert(Value::from(5u64), Value::T);
+    let keys = (90..100u64).chain([5]).map(Value::from).collect::<Vec<_>>();
+    assert_equal!(table.keys(), keys);
+    assert_equal!(table.get(&Value::from(95u64)), Some(Value::T));
+}
+
+#[test]
+fn test_tests_compare_keys() {
+    let key = list([Value::symbol("a"), Value::string("b")]);
+    let copy = list([Value::symbol("a"), Value::string("b")]);
+
+    let eq = HashTable::new(Test::Eq);
+    eq.insert(key.clone(), Value::T);
+    assert_equal!(eq.get(&key), Some(Value::T));
+    assert_equal!(eq.get(&copy), None);
+    let string = Value::string("x");
+    eq.insert(string.clone(), Value::T);
+    assert_equal!(eq.get(&string), Some(Value::T));
+    assert_equal!(eq.get(&Value::string("x")), None);
+
+    let equal = HashTable::new(Test::Equal);
+    equal.insert(key, Value::T);
+    assert_equal!(equal.get(&copy), Some(Value::T));
+    assert_equal!(equal.get(&Value::string("x")), None);
+}
+
+#[test]
+fn test_clones_share_the_table() {
+    let table = HashTable::new(Test::Equal);
+    let value = Value::HashTable(table.clone());
+    table.insert(Value::string("k"), Value::from(1u64));
+    assert_equal!(prin1(&value), r#"#s(hash-table test equal data ("k" 1))"#);
+    assert_equal!(value == Value::HashTable(table), true);
+    assert_equal!(value == Value::HashTable(HashTable::new(Test::Equal)), false);
+    assert_equal!(prin1(&Value::HashTable(HashTable::new(Test::Eql))), "#s(hash-table)");
+}
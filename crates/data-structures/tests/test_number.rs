@@ -0,0 +1,100 @@
+use std::str::FromStr;
+
+use k9::assert_equal;
+use minilisp_data_structures::{BigInteger, Number, Rational};
+
+fn big(literal: &str) -> BigInteger {
+    BigInteger::from_str(literal).unwrap()
+}
+
+#[test]
+fn test_big_integer_arithmetic() {
+    let a = big("123456789012345678901234567890");
+    let b = big("-987654321098765432109876543210");
+    assert_equal!((a.clone() + b.clone()).to_string(), "-864197532086419753208641975320");
+    assert_equal!((a.clone() - b.clone()).to_string(), "1111111110111111111011111111100");
+    assert_equal!(
+        (a.clone() * b.clone()).to_string(),
+        "-121932631137021795226185032733622923332237463801111263526900"
+    );
+    let (quotient, remainder) = b.div_rem(&a);
+    assert_equal!(quotient.to_string(), "-8");
+    assert_equal!(remainder.to_string(), "-9000000000900000000090");
+    assert_equal!(a.gcd(&b).to_string(), "9000000000900000000090");
+    assert_equal!(b < a, true);
+    assert_equal!(big("-9223372036854775808").to_i64(), Some(i64::MIN));
+    assert_equal!(big("9223372036854775808").to_i64(), None);
+}
+
+#[test]
+fn test_rationals_are_in_lowest_terms() {
+    let rational = Rational::new(big("6"), big("-4"));
+    assert_equal!(rational.to_string(), "-3/2");
+    assert_equal!(rational.numerator().to_string(), "-3");
+    assert_equal!(rational.denominator().to_string(), "2");
+    assert_equal!(rational == Rational::new(big("-3"), big("2")), true);
+    assert_equal!(rational < Rational::new(big("-4"), big("3")), true);
+}
+
+#[test]
+fn test_numeric_tower_promotion() {
+    assert_equal!(
+        Number::UnsignedInteger(1) - Number::UnsignedInteger(2),
+        Number::Integer(-1)
+    );
+    assert_equal!(Number::Integer(2) + Number::Float(0.5), Number::Float(2.5));
+    assert_equal!(
+        Number::Integer(i64::MAX) + Number::UnsignedInteger(1),
+        Number::BigInteger(big("9223372036854775808"))
+    );
+    assert_equal!(
+        Number::BigInteger(big("9223372036854775808")) - Number::UnsignedInteger(1),
+        Number::Integer(i64::MAX)
+    );
+    assert_equal!(
+        Number::Integer(i64::MIN) / Number::Integer(-1),
+        Number::BigInteger(big("9223372036854775808"))
+    );
+    assert_equal!(
+        Number::UnsignedInteger(2) / Number::UnsignedInteger(4),
+        Number::Rational(Rational::new(big("1"), big("2")))
+    );
+    assert_equal!(
+        Number::Rational(Rational::new(big("1"), big("2"))) * Number::Integer(-4),
+        Number::Integer(-2)
+    );
+    assert_equal!(-Number::Integer(i64::MIN), Number::BigInteger(big("9223372036854775808")));
+}
+
+#[test]
+fn test_checked_arithmetic() {
+    use minilisp_data_structures::ArithmeticError;
+
+    assert_equal!(
+        Number::Integer(1).checked_div(Number::UnsignedInteger(0)),
+        Err(ArithmeticError::DivisionByZero)
+    );
+    assert_equal!(
+        Number::Float(f64::MAX).checked_add(Number::Float(f64::MAX)),
+        Err(ArithmeticError::Overflow)
+    );
+    assert_equal!(
+        Number::Float(f64::INFINITY).checked_add(Number::UnsignedInteger(1)),
+        Ok(Number::Float(f64::INFINITY))
+    );
+    assert_equal!(
+        Number::Float(0.0).checked_mul(Number::Float(f64::INFINITY)),
+        Err(ArithmeticError::NotANumber)
+    );
+    assert_equal!(ArithmeticError::Overflow.name(), "overflow-error");
+
+    // squares 2^32 until it is wider than INTEGER_WIDTH bits
+    let mut number = Number::Integer(1 << 32);
+    let error = loop {
+        match number.clone().checked_mul(number) {
+            Ok(square) => number = square,
+            Err(error) => break error,
+        }
+    };
+    assert_equal!(error, ArithmeticError::Overflow);
+}
@@ -0,0 +1,63 @@
+use k9::assert_equal;
+use minilisp_data_structures::{Symbol, Value};
+
+#[test]
+fn test_symbols_of_the_same_name_share_their_name() {
+    let first = Symbol::new("test-symbol-shared");
+    let second = Symbol::new(String::from("test-symbol-shared"));
+    assert_equal!(first.id(), second.id());
+    assert_equal!(std::ptr::eq(first.symbol(), second.symbol()), true);
+    assert_equal!(first, second);
+    assert_equal!(first.quote() == second, false);
+    assert_equal!(first.quote().unquote(), second);
+    assert_equal!(first.quote().id(), second.id());
+    assert_equal!(first.quote().is_quoted(), true);
+}
+
+#[test]
+fn test_symbols_of_different_names() {
+    let a = Symbol::new("test-symbol-a");
+    let b = Symbol::new("test-symbol-b");
+    assert_equal!(a == b, false);
+    assert_equal!(a < b, true);
+    assert_equal!(Symbol::default().symbol(), "");
+    assert_equal!(Symbol::default(), Symbol::new(""));
+}
+
+#[test]
+fn test_interned() {
+    assert_equal!(Symbol::interned("test-symbol-never-built"), None);
+    let symbol = Value::symbol("test-symbol-built");
+    assert_equal!(
+        Symbol::interned("test-symbol-built").map(Value::Symbol),
+        Some(symbol)
+    );
+}
+
+#[test]
+fn test_keywords() {
+    assert_equal!(Symbol::new(":test").is_keyword(), true);
+    assert_equal!(Symbol::new("test").is_keyword(), false);
+    assert_equal!(Symbol::new("a:b").is_keyword(), false);
+}
+
+#[test]
+fn test_property_lists() {
+    let symbol = Symbol::new("test-symbol-plist");
+    assert_equal!(symbol.plist(), Value::Nil);
+    symbol.put(&Value::quoted_symbol("color"), &Value::from("red"));
+    symbol.put(&Value::symbol("size"), &Value::unsigned_integer(3u32));
+    symbol.put(&Value::symbol("color"), &Value::from("green"));
+    assert_equal!(
+        Symbol::new("test-symbol-plist").get(&Value::symbol("color")),
+        Value::from("green")
+    );
+    assert_equal!(symbol.quote().get(&Value::quoted_symbol("size")), Value::unsigned_integer(3u32));
+    assert_equal!(symbol.get(&Value::symbol("weight")), Value::Nil);
+    assert_equal!(symbol.plist().to_string(), r#"(color "green" size 3)"#);
+
+    let text = String::from(r#"(moons "one")"#);
+    symbol.set_plist(&Value::list([Value::symbol("moons"), Value::String(text[8..11].into())]));
+    drop(text);
+    assert_equal!(symbol.get(&Value::symbol("moons")), Value::from("one"));
+}
@@ -18,6 +18,35 @@ fn test_string() {
     assert_debug_equal!(Value::string("string"), r#""string""#);
 }
 #[test]
+fn test_string_with_escapes() {
+    assert_display_equal!(
+        Value::string("a \"b\"\n\\c\u{7}"),
+        r#""a \"b\"\n\\c\u{7}""#
+    );
+    assert_debug_equal!(Value::string("tab\t"), r#""tab\t""#);
+}
+#[test]
+fn test_char() {
+    assert_display_equal!(Value::char('a'), "?a");
+    assert_debug_equal!(Value::char('a'), "?a");
+    assert_display_equal!(Value::char('\n'), r#"?\n"#);
+    assert_display_equal!(Value::char(' '), r#"?\s"#);
+    assert_display_equal!(Value::char('\x01'), r#"?\C-a"#);
+    assert_display_equal!(Value::char('\0'), r#"?\^@"#);
+    assert_display_equal!(Value::char('('), r#"?\("#);
+    assert_display_equal!(Value::char('é'), "?é");
+}
+#[test]
+fn test_vector() {
+    let vector = Value::vector([
+        Value::unsigned_integer(1u32),
+        Value::from("a"),
+        Value::vector([]),
+    ]);
+    assert_display_equal!(vector, r#"[1 "a" []]"#);
+    assert_debug_equal!(vector, r#"[1 "a" []]"#);
+}
+#[test]
 fn test_symbol() {
     assert_display_equal!(Value::symbol("symbol"), "symbol");
     assert_debug_equal!(Value::symbol("symbol"), "symbol");
@@ -0,0 +1,456 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+use minilisp_data_structures::{list, Value};
+use pest::iterators::Pair;
+use pest::Parser;
+
+use crate::{
+    dispatch, list_value, pair_to_value, quoted_value, syntax_error, Error, MinilispSource,
+    Result, Rule, Source, Span, SpanPosition,
+};
+
+/// Kind of a [`SyntaxNode`] or [`SyntaxToken`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum SyntaxKind {
+    File,
+    List,
+    Vector,
+    Quoted,
+    Quasiquote,
+    Unquote,
+    UnquoteSplicing,
+    Dispatch,
+
+    OpenParen,
+    CloseParen,
+    OpenBracket,
+    CloseBracket,
+    Quote,
+    Backquote,
+    Comma,
+    CommaAt,
+    Dot,
+    DispatchPrefix,
+
+    Symbol,
+    T,
+    Nil,
+    Integer,
+    Float,
+    RadixInteger,
+    Rational,
+    Character,
+    String,
+
+    Whitespace,
+    LineComment,
+    BlockComment,
+    DatumComment,
+}
+impl SyntaxKind {
+    /// `true` for whitespace and comments.
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::Whitespace
+                | SyntaxKind::LineComment
+                | SyntaxKind::BlockComment
+                | SyntaxKind::DatumComment
+        )
+    }
+
+    /// `true` for the kinds that lower to a [`Value`].
+    pub fn is_datum(&self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::List
+                | SyntaxKind::Vector
+                | SyntaxKind::Quoted
+                | SyntaxKind::Quasiquote
+                | SyntaxKind::Unquote
+                | SyntaxKind::UnquoteSplicing
+                | SyntaxKind::Dispatch
+        ) || self.atom_rule().is_some()
+    }
+
+    fn atom_rule(&self) -> Option<Rule> {
+        Some(match self {
+            SyntaxKind::Symbol => Rule::symbol,
+            SyntaxKind::T => Rule::t,
+            SyntaxKind::Nil => Rule::nil,
+            SyntaxKind::Integer => Rule::integer,
+            SyntaxKind::Float => Rule::float,
+            SyntaxKind::RadixInteger => Rule::radix_integer,
+            SyntaxKind::Rational => Rule::rational,
+            SyntaxKind::Character => Rule::character,
+            SyntaxKind::String => Rule::double_quoted_string,
+            _ => return None,
+        })
+    }
+}
+
+/// Leaf of the concrete syntax tree: a slice of the source text.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct SyntaxToken<'a> {
+    pub kind: SyntaxKind,
+    pub text: &'a str,
+    pub start_pos: SpanPosition,
+    pub end_pos: SpanPosition,
+}
+
+/// Inner node of the concrete syntax tree, such as a list along with
+/// its parens, quote sugar, whitespace and comments.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct SyntaxNode<'a> {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement<'a>>,
+    pub start_pos: SpanPosition,
+    pub end_pos: SpanPosition,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum SyntaxElement<'a> {
+    Node(SyntaxNode<'a>),
+    Token(SyntaxToken<'a>),
+}
+
+/// Lossless concrete syntax tree of a program.
+///
+/// Unlike [`Value`], the tree keeps every byte of the source, i.e.
+/// whitespace, comments, parens and quote sugar, so that printing it
+/// yields the original text byte-for-byte. Tools can walk or rewrite
+/// it and lower datums to [`Value`] with [`SyntaxElement::lower`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SyntaxTree<'a> {
+    pub source: Source<'a>,
+    pub root: SyntaxNode<'a>,
+}
+
+/// Parses `input` into a [`SyntaxTree`].
+pub fn parse_cst<'a>(
+    input: &'a str,
+    filename: Option<&'a str>,
+) -> Result<'a, SyntaxTree<'a>> {
+    let source = Source::new(input, filename);
+    let file = MinilispSource::parse(Rule::file, input)
+        .map_err(|error| syntax_error(error, &source))?
+        .next()
+        .expect("file");
+    let mut builder = Builder {
+        input,
+        source: source.clone(),
+        offset: 0,
+        position: SpanPosition { line: 1, column: 1 },
+    };
+    let root = builder.node(SyntaxKind::File, file)?;
+    Ok(SyntaxTree { source, root })
+}
+
+impl<'a> SyntaxTree<'a> {
+    /// Top-level datums, skipping whitespace and comments.
+    pub fn forms(&self) -> impl Iterator<Item = &SyntaxElement<'a>> {
+        self.root.datums()
+    }
+
+    /// Lowers every top-level datum to a [`Value`], as
+    /// [`parse_program`](crate::parse_program) reads them.
+    pub fn lower(&self) -> Result<'a, Vec<Value<'a>>> {
+        self.forms().map(SyntaxElement::lower).collect()
+    }
+
+    /// [`Span`] of `element` within this tree's source.
+    pub fn span(&self, element: &SyntaxElement<'a>) -> Span<'a> {
+        Span {
+            input: match element {
+                SyntaxElement::Token(token) => Cow::from(token.text),
+                SyntaxElement::Node(node) => Cow::from(node.to_string()),
+            },
+            name: Some(format!("{:?}", element.kind())),
+            start_pos: element.start_pos().clone(),
+            end_pos: element.end_pos().clone(),
+            source: self.source.clone(),
+            inner: None,
+            comments: Vec::new(),
+        }
+    }
+}
+
+impl<'a> SyntaxNode<'a> {
+    /// Children that lower to a [`Value`].
+    pub fn datums(&self) -> impl Iterator<Item = &SyntaxElement<'a>> {
+        self.children.iter().filter(|child| child.kind().is_datum())
+    }
+
+    /// Every token under this node, in source order.
+    pub fn tokens(&self) -> Vec<&SyntaxToken<'a>> {
+        let mut tokens = Vec::<&SyntaxToken<'a>>::new();
+        for child in self.children.iter() {
+            match child {
+                SyntaxElement::Token(token) => tokens.push(token),
+                SyntaxElement::Node(node) => tokens.extend(node.tokens()),
+            }
+        }
+        tokens
+    }
+}
+
+impl<'a> SyntaxElement<'a> {
+    pub fn kind(&self) -> SyntaxKind {
+        match self {
+            SyntaxElement::Node(node) => node.kind,
+            SyntaxElement::Token(token) => token.kind,
+        }
+    }
+
+    pub fn start_pos(&self) -> &SpanPosition {
+        match self {
+            SyntaxElement::Node(node) => &node.start_pos,
+            SyntaxElement::Token(token) => &token.start_pos,
+        }
+    }
+
+    pub fn end_pos(&self) -> &SpanPosition {
+        match self {
+            SyntaxElement::Node(node) => &node.end_pos,
+            SyntaxElement::Token(token) => &token.end_pos,
+        }
+    }
+
+    /// Lowers this datum to the [`Value`] the parser reads from it.
+    pub fn lower(&self) -> Result<'a, Value<'a>> {
+        match self {
+            SyntaxElement::Token(token) => token.lower(),
+            SyntaxElement::Node(node) => node.lower(),
+        }
+    }
+}
+
+impl<'a> SyntaxToken<'a> {
+    fn lower(&self) -> Result<'a, Value<'a>> {
+        let rule = self.kind.atom_rule().ok_or_else(|| cannot_lower(self.kind))?;
+        let pair = MinilispSource::parse(rule, self.text)
+            .map_err(|error| syntax_error(error, &Source::new(self.text, None)))?
+            .next()
+            .expect("atom");
+        if pair.as_str().len() != self.text.len() {
+            return Err(Error::new(
+                format!("invalid {:?} {:?}", self.kind, self.text),
+                None,
+            ));
+        }
+        pair_to_value(pair)
+    }
+}
+
+impl<'a> SyntaxNode<'a> {
+    fn lower(&self) -> Result<'a, Value<'a>> {
+        let mut children = self.children.iter().filter(|child| !child.kind().is_trivia());
+        Ok(match self.kind {
+            SyntaxKind::List => {
+                let mut items = Vec::<Value<'a>>::new();
+                let mut dotted_tail = None;
+                while let Some(child) = children.next() {
+                    match child.kind() {
+                        SyntaxKind::OpenParen | SyntaxKind::CloseParen => {},
+                        SyntaxKind::Dot =>
+                            dotted_tail = Some(
+                                children
+                                    .next()
+                                    .ok_or_else(|| cannot_lower(SyntaxKind::Dot))?
+                                    .lower()?,
+                            ),
+                        _ => items.push(child.lower()?),
+                    }
+                }
+                list_value(items, dotted_tail)
+            },
+            SyntaxKind::Vector => Value::vector(
+                self.datums()
+                    .map(SyntaxElement::lower)
+                    .collect::<Result<'a, Vec<Value<'a>>>>()?,
+            ),
+            SyntaxKind::Quoted => quoted_value(
+                self.datums().next().ok_or_else(|| cannot_lower(self.kind))?.lower()?,
+            ),
+            SyntaxKind::Quasiquote
+            | SyntaxKind::Unquote
+            | SyntaxKind::UnquoteSplicing => {
+                let symbol = match self.kind {
+                    SyntaxKind::Quasiquote => "backquote",
+                    SyntaxKind::Unquote => ",",
+                    _ => ",@",
+                };
+                let datum = self.datums().next().ok_or_else(|| cannot_lower(self.kind))?;
+                list([Value::symbol(symbol), datum.lower()?])
+            },
+            SyntaxKind::Dispatch => match (children.next(), self.datums().next()) {
+                (Some(SyntaxElement::Token(prefix)), Some(datum)) =>
+                    dispatch::expand(prefix.text, datum.lower()?)?,
+                _ => return Err(cannot_lower(self.kind)),
+            },
+            kind => return Err(cannot_lower(kind)),
+        })
+    }
+}
+
+fn cannot_lower<'a>(kind: SyntaxKind) -> Error<'a> {
+    Error::new(format!("cannot lower {:?} to a value", kind), None)
+}
+
+/// Builds the tree in source order, keeping track of the line and
+/// column so that every position is computed in a single pass.
+struct Builder<'a> {
+    input: &'a str,
+    source: Source<'a>,
+    offset: usize,
+    position: SpanPosition,
+}
+impl<'a> Builder<'a> {
+    fn element(&mut self, pair: Pair<'a, Rule>) -> Result<'a, Option<SyntaxElement<'a>>> {
+        let node = match pair.as_rule() {
+            Rule::value => match pair.into_inner().next() {
+                Some(pair) => return self.element(pair),
+                None => return Ok(None),
+            },
+            Rule::EOI => return Ok(None),
+            Rule::sexpr => Some(SyntaxKind::List),
+            Rule::vector => Some(SyntaxKind::Vector),
+            Rule::quoted => Some(SyntaxKind::Quoted),
+            Rule::quasiquote => Some(SyntaxKind::Quasiquote),
+            Rule::unquote => Some(SyntaxKind::Unquote),
+            Rule::unquote_splicing => Some(SyntaxKind::UnquoteSplicing),
+            Rule::dispatch => Some(SyntaxKind::Dispatch),
+            _ => None,
+        };
+        if let Some(kind) = node {
+            return Ok(Some(self.node(kind, pair)?.into()));
+        }
+        let kind = match pair.as_rule() {
+            // comments skipped implicitly carry no inner pairs
+            Rule::COMMENT if pair.as_str().starts_with("#|") => SyntaxKind::BlockComment,
+            Rule::COMMENT if pair.as_str().starts_with("#;") => SyntaxKind::DatumComment,
+            Rule::COMMENT => SyntaxKind::LineComment,
+            Rule::open_paren => SyntaxKind::OpenParen,
+            Rule::close_paren => SyntaxKind::CloseParen,
+            Rule::open_bracket => SyntaxKind::OpenBracket,
+            Rule::close_bracket => SyntaxKind::CloseBracket,
+            Rule::quote => SyntaxKind::Quote,
+            Rule::backquote => SyntaxKind::Backquote,
+            Rule::comma => SyntaxKind::Comma,
+            Rule::comma_at => SyntaxKind::CommaAt,
+            Rule::dot => SyntaxKind::Dot,
+            Rule::dispatch_prefix => SyntaxKind::DispatchPrefix,
+            Rule::symbol => SyntaxKind::Symbol,
+            Rule::t => SyntaxKind::T,
+            Rule::nil => SyntaxKind::Nil,
+            Rule::integer => SyntaxKind::Integer,
+            Rule::float => SyntaxKind::Float,
+            Rule::radix_integer => SyntaxKind::RadixInteger,
+            Rule::rational => SyntaxKind::Rational,
+            Rule::character => SyntaxKind::Character,
+            Rule::double_quoted_string => SyntaxKind::String,
+            rule =>
+                return Err(Error::new(
+                    format!("unexpected {:?} within a datum", rule),
+                    Some(Span::from_pest_span(pair.as_span(), self.source.clone())),
+                )),
+        };
+        let span = pair.as_span();
+        Ok(self.token(kind, span.start(), span.end()).map(SyntaxElement::from))
+    }
+
+    /// Node of `kind` spanning `pair`, with the text between its
+    /// children kept as whitespace tokens.
+    fn node(&mut self, kind: SyntaxKind, pair: Pair<'a, Rule>) -> Result<'a, SyntaxNode<'a>> {
+        let span = pair.as_span();
+        let start_pos = self.position.clone();
+        let mut children = Vec::<SyntaxElement<'a>>::new();
+        for pair in pair.into_inner().flat_map(|pair| match pair.as_rule() {
+            Rule::statement => pair.into_inner().collect::<Vec<_>>(),
+            _ => vec![pair],
+        }) {
+            children.extend(self.whitespace(pair.as_span().start()));
+            children.extend(self.element(pair)?);
+        }
+        children.extend(self.whitespace(span.end()));
+        Ok(SyntaxNode {
+            kind,
+            children,
+            start_pos,
+            end_pos: self.position.clone(),
+        })
+    }
+
+    fn whitespace(&mut self, end: usize) -> Option<SyntaxElement<'a>> {
+        self.token(SyntaxKind::Whitespace, self.offset, end)
+            .map(SyntaxElement::from)
+    }
+
+    fn token(
+        &mut self,
+        kind: SyntaxKind,
+        start: usize,
+        end: usize,
+    ) -> Option<SyntaxToken<'a>> {
+        debug_assert_eq!(start, self.offset);
+        if start >= end {
+            return None;
+        }
+        let text = &self.input[start..end];
+        let start_pos = self.position.clone();
+        for c in text.chars() {
+            if c == '\n' {
+                self.position.line += 1;
+                self.position.column = 1;
+            } else {
+                self.position.column += 1;
+            }
+        }
+        self.offset = end;
+        Some(SyntaxToken {
+            kind,
+            text,
+            start_pos,
+            end_pos: self.position.clone(),
+        })
+    }
+}
+
+impl<'a> From<SyntaxNode<'a>> for SyntaxElement<'a> {
+    fn from(node: SyntaxNode<'a>) -> SyntaxElement<'a> {
+        SyntaxElement::Node(node)
+    }
+}
+impl<'a> From<SyntaxToken<'a>> for SyntaxElement<'a> {
+    fn from(token: SyntaxToken<'a>) -> SyntaxElement<'a> {
+        SyntaxElement::Token(token)
+    }
+}
+
+impl Display for SyntaxToken<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+impl Display for SyntaxNode<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for child in self.children.iter() {
+            write!(f, "{}", child)?;
+        }
+        Ok(())
+    }
+}
+impl Display for SyntaxElement<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            SyntaxElement::Node(node) => write!(f, "{}", node),
+            SyntaxElement::Token(token) => write!(f, "{}", token),
+        }
+    }
+}
+impl Display for SyntaxTree<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.root)
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, RwLock};
+
+use minilisp_data_structures::hash_table::Test;
+use minilisp_data_structures::{list, HashTable, Value};
+
+use crate::{Error, Result};
+
+/// Expands the datum following a `#name` dispatch prefix into the
+/// value the reader yields in its place.
+pub type DispatchMacro = for<'a> fn(Value<'a>) -> Result<'a, Value<'a>>;
+
+static DISPATCH_TABLE: LazyLock<RwLock<BTreeMap<String, DispatchMacro>>> =
+    LazyLock::new(|| {
+        RwLock::new(BTreeMap::from([
+            ("'".to_string(), function as DispatchMacro),
+            ("s".to_string(), record as DispatchMacro),
+        ]))
+    });
+
+/// Registers `function` as the expansion of `#name` followed by a
+/// datum, e.g. `date` for `#date"2026-01-01"`, returning the macro it
+/// replaces.
+///
+/// Named dispatch must be immediately followed by a list, vector or
+/// string; `#'` is followed by any datum. The table is global, so a
+/// macro registered here applies to every parser entry point.
+pub fn register_dispatch_macro(
+    name: &str,
+    function: DispatchMacro,
+) -> Option<DispatchMacro> {
+    DISPATCH_TABLE
+        .write()
+        .expect("dispatch table")
+        .insert(name.to_string(), function)
+}
+
+pub fn unregister_dispatch_macro(name: &str) -> Option<DispatchMacro> {
+    DISPATCH_TABLE.write().expect("dispatch table").remove(name)
+}
+
+pub fn dispatch_macro(name: &str) -> Option<DispatchMacro> {
+    DISPATCH_TABLE.read().expect("dispatch table").get(name).copied()
+}
+
+/// Expands `datum` with the macro registered for `prefix`, i.e. the
+/// dispatch text including its leading `#`.
+pub(crate) fn expand<'a>(prefix: &str, datum: Value<'a>) -> Result<'a, Value<'a>> {
+    let name = prefix.strip_prefix('#').unwrap_or(prefix);
+    match dispatch_macro(name) {
+        Some(function) => function(datum),
+        None => Err(Error::new(format!("unknown reader macro {}", prefix), None)),
+    }
+}
+
+/// `#'fn` reads as `(function fn)`.
+fn function<'a>(datum: Value<'a>) -> Result<'a, Value<'a>> {
+    Ok(list([Value::symbol("function"), datum]))
+}
+
+/// `#s(type slot...)` reads as a vector holding the record type
+/// followed by its slots, the layout of an elisp record, except for
+/// `#s(hash-table test TEST data (KEY VALUE...))`, which reads as a
+/// hash table.
+fn record<'a>(datum: Value<'a>) -> Result<'a, Value<'a>> {
+    match &datum {
+        Value::List(cell) | Value::QuotedList(cell) if cell.is_proper() => {
+            let values = cell.values();
+            match values.first() {
+                Some(Value::Symbol(symbol)) if symbol.symbol() == "hash-table" =>
+                    hash_table(&values[1..], &datum),
+                _ => Ok(Value::vector(values)),
+            }
+        },
+        _ => Err(Error::new(format!("invalid record syntax: #s{}", datum), None)),
+    }
+}
+
+/// Reads the `test` and `data` properties of `#s(hash-table ...)`,
+/// ignoring the others, such as `size`.
+fn hash_table<'a>(properties: &[Value<'a>], datum: &Value<'a>) -> Result<'a, Value<'a>> {
+    let invalid = || Error::new(format!("invalid hash table syntax: #s{}", datum), None);
+    let mut test = Test::default();
+    let mut data = Vec::new();
+    for property in properties.chunks(2) {
+        match property {
+            [Value::Symbol(name), Value::Symbol(value)] if name.symbol() == "test" =>
+                test = Test::from_name(value.symbol()).ok_or_else(invalid)?,
+            [Value::Symbol(name), Value::List(cell)] if name.symbol() == "data" =>
+                data = cell.values(),
+            [Value::Symbol(name), _] if !matches!(name.symbol(), "test" | "data") => {},
+            _ => return Err(invalid()),
+        }
+    }
+    if data.len() % 2 != 0 {
+        return Err(invalid());
+    }
+    let table = HashTable::new(test);
+    for entry in data.chunks(2) {
+        table.insert(entry[0].clone(), entry[1].clone());
+    }
+    Ok(Value::HashTable(table))
+}
@@ -3,7 +3,7 @@ use std::fmt::{Debug, Display, Formatter};
 pub use minilisp_formatter::Error as FormatterError;
 pub use minilisp_util::{color, with_caller, Caller, Traceback};
 
-use crate::Span;
+use crate::{Source, Span};
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct Error<'a> {
@@ -20,6 +20,47 @@ impl<'a> Error<'a> {
             callers: Vec::new(),
         }
     }
+
+    pub fn span(&self) -> Option<Span<'a>> {
+        self.info.clone()
+    }
+
+    /// Replaces the [`Source`] of this error's span, e.g. to record
+    /// the filename of the input it was raised from.
+    pub fn with_source(mut self, source: Source<'a>) -> Self {
+        if let Some(info) = self.info.as_mut() {
+            info.source = source;
+        }
+        self
+    }
+
+    /// Points this error at `span` unless it already has one.
+    pub(crate) fn or_span(mut self, span: Span<'a>) -> Self {
+        if self.info.is_none() {
+            self.info = Some(span);
+        }
+        self
+    }
+
+    /// Copies this error into one that borrows nothing.
+    pub fn into_owned<'o>(self) -> Error<'o> {
+        Error {
+            message: self.message,
+            info: self.info.map(|span| {
+                let source = span.source.to_owned();
+                span.into_owned(&source)
+            }),
+            callers: self.callers,
+        }
+    }
+
+    /// Moves the span of this error like [`Span::shift`].
+    pub(crate) fn shift(mut self, lines: usize, columns: usize) -> Self {
+        if let Some(info) = self.info.as_mut() {
+            info.shift(lines, columns);
+        }
+        self
+    }
 }
 impl std::error::Error for Error<'_> {}
 impl<'a> Traceback for Error<'a> {
@@ -0,0 +1,192 @@
+use std::borrow::Cow;
+
+use pest::iterators::Pair;
+
+use crate::{Error, Result, Rule, Source, Span};
+
+/// Removes the backslashes that quote characters of a symbol name,
+/// as in `foo\ bar` or `\1`. Names without any are borrowed.
+pub fn unescape_symbol(name: &str) -> Cow<'_, str> {
+    if !name.contains('\\') {
+        return Cow::from(name);
+    }
+    let mut unescaped = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+    Cow::from(unescaped)
+}
+
+/// Decodes the escape sequences of a `string` pair.
+///
+/// Supports `\n`, `\t`, `\r`, `\0`, `\a`, `\b`, `\f`, `\v`, `\e`,
+/// `\s`, `\\`, `\"`, `\'`, `\xHH`, `\uXXXX`, `\u{X...}` and a
+/// backslash before a newline, which continues the string on the
+/// next line. Strings without escapes are borrowed from the input.
+pub fn unescape_string<'a>(pair: &Pair<'a, Rule>) -> Result<'a, Cow<'a, str>> {
+    let string = pair.as_str();
+    if !string.contains('\\') {
+        return Ok(Cow::from(string));
+    }
+    let offset = pair.as_span().start();
+    let mut unescaped = String::with_capacity(string.len());
+    let mut chars = string.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        let invalid = |end: usize, reason: &str| -> Error<'a> {
+            let input = pair.get_input();
+            let span = pest::Span::new(input, offset + start, offset + end)
+                .expect("escape sequence within input");
+            Error::new(
+                format!("{}: {:#?}", reason, &string[start..end]),
+                Some(Span::from_pest_span(span, Source::new(input, None))),
+            )
+        };
+        let (index, escape) = match chars.next() {
+            Some(next) => next,
+            None => return Err(invalid(string.len(), "unterminated escape sequence")),
+        };
+        let end = index + escape.len_utf8();
+        if let Some(c) = simple_escape(escape) {
+            unescaped.push(c);
+            continue;
+        }
+        match escape {
+            '0' => unescaped.push('\0'),
+            '\\' | '"' | '\'' => unescaped.push(escape),
+            '\n' => {},
+            'x' | 'u' => {
+                let braced = escape == 'u' && chars.peek().map(|(_, c)| *c) == Some('{');
+                if braced {
+                    chars.next();
+                }
+                let mut digits = String::new();
+                let mut end = end;
+                while let Some((index, c)) = chars.peek().copied() {
+                    let complete = match (escape, braced) {
+                        ('x', _) => digits.len() == 2,
+                        ('u', false) => digits.len() == 4,
+                        _ => c == '}',
+                    };
+                    if complete || !c.is_ascii_hexdigit() {
+                        break;
+                    }
+                    digits.push(c);
+                    end = index + 1;
+                    chars.next();
+                }
+                if braced {
+                    match chars.next() {
+                        Some((index, '}')) => end = index + 1,
+                        _ => return Err(invalid(end, "unterminated unicode escape")),
+                    }
+                }
+                let expected = match (escape, braced) {
+                    ('x', _) => 2..=2,
+                    ('u', false) => 4..=4,
+                    _ => 1..=6,
+                };
+                if !expected.contains(&digits.len()) {
+                    return Err(invalid(end, "invalid escape sequence"));
+                }
+                match u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32) {
+                    Some(c) => unescaped.push(c),
+                    None => return Err(invalid(end, "invalid unicode code point")),
+                }
+            },
+            _ => return Err(invalid(end, "invalid escape sequence")),
+        }
+    }
+    Ok(Cow::from(unescaped))
+}
+
+/// Decodes a `character` pair such as `?a`, `?\n`, `?\x41`,
+/// `?\101` or `?\C-a`.
+///
+/// Any character after a backslash that is not a known escape stands
+/// for itself, so `?\(` is `(`. The `\C-` and `\^` prefixes turn
+/// `@`, letters, `[`, `\`, `]`, `^` and `_` into control characters
+/// and `?` into DEL.
+pub fn unescape_char<'a>(pair: &Pair<'a, Rule>) -> Result<'a, char> {
+    let invalid = |reason: &str| -> Error<'a> {
+        Error::new(
+            format!("{}: {:#?}", reason, pair.as_str()),
+            Some(Span::from_pest_span(
+                pair.as_span(),
+                Source::new(pair.get_input(), None),
+            )),
+        )
+    };
+    let mut body = &pair.as_str()[1..];
+    let mut controls = 0;
+    // a trailing `\^` is the escaped `^` of `?\^\^`
+    while let Some(rest) = body
+        .strip_prefix("\\C-")
+        .or_else(|| body.strip_prefix("\\^"))
+        .filter(|rest| !rest.is_empty())
+    {
+        controls += 1;
+        body = rest;
+    }
+    let mut c = match body.strip_prefix('\\') {
+        None => body.chars().next().expect("character"),
+        Some(escape) => {
+            let prefix = escape.chars().next().expect("escape").len_utf8();
+            let code = match escape.split_at(prefix) {
+                ("x" | "u" | "U", digits) if !digits.is_empty() =>
+                    Some(u32::from_str_radix(digits, 16)),
+                ("N", digits) => Some(u32::from_str_radix(
+                    digits.trim_start_matches("{U+").trim_end_matches('}'),
+                    16,
+                )),
+                (digit, _) if digit.starts_with(|c: char| c.is_digit(8)) =>
+                    Some(u32::from_str_radix(escape, 8)),
+                _ => None,
+            };
+            match code {
+                Some(code) => code
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| invalid("invalid unicode code point"))?,
+                None => {
+                    let escape = escape.chars().next().expect("escape");
+                    match escape {
+                        'd' => '\x7f',
+                        escape => simple_escape(escape).unwrap_or(escape),
+                    }
+                },
+            }
+        },
+    };
+    for _ in 0..controls {
+        c = match c {
+            '?' => '\x7f',
+            'a'..='z' => ((c as u8) & 0x1f) as char,
+            '@'..='_' => ((c as u8) & 0x1f) as char,
+            _ => return Err(invalid("invalid control character")),
+        };
+    }
+    Ok(c)
+}
+
+fn simple_escape(escape: char) -> Option<char> {
+    match escape {
+        'n' => Some('\n'),
+        't' => Some('\t'),
+        'r' => Some('\r'),
+        'a' => Some('\x07'),
+        'b' => Some('\x08'),
+        'f' => Some('\x0c'),
+        'v' => Some('\x0b'),
+        'e' => Some('\x1b'),
+        's' => Some(' '),
+        _ => None,
+    }
+}
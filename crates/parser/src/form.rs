@@ -0,0 +1,58 @@
+use minilisp_data_structures::Value;
+
+use crate::{SourceMap, Span};
+
+/// A top-level form of a program paired with the [`Span`] it was
+/// read from.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Form<'a> {
+    pub value: Value<'a>,
+    pub span: Span<'a>,
+    /// Spans of every list and atom within this form.
+    pub spans: SourceMap<'a>,
+}
+impl<'a> Form<'a> {
+    pub fn new(value: Value<'a>, span: Span<'a>) -> Form<'a> {
+        Form {
+            value,
+            span,
+            spans: SourceMap::new(),
+        }
+    }
+
+    pub fn with_spans(mut self, spans: SourceMap<'a>) -> Form<'a> {
+        self.spans = spans;
+        self
+    }
+
+    pub fn value(&self) -> Value<'a> {
+        self.value.clone()
+    }
+
+    pub fn span(&self) -> Span<'a> {
+        self.span.clone()
+    }
+
+    pub fn spans(&self) -> &SourceMap<'a> {
+        &self.spans
+    }
+
+    /// Copies this form into one that borrows nothing, like
+    /// [`Value::to_owned`], keeping the spans of its lists.
+    pub fn into_owned<'o>(self) -> Form<'o> {
+        let source = self.span.source.to_owned();
+        let mut spans = SourceMap::new();
+        let value = self.spans.to_owned_in(&self.value, &source, &mut spans);
+        Form {
+            value,
+            span: self.span.into_owned(&source),
+            spans,
+        }
+    }
+
+    /// Moves the spans of this form like [`Span::shift`].
+    pub(crate) fn shift(&mut self, lines: usize, columns: usize) {
+        self.span.shift(lines, columns);
+        self.spans.shift(lines, columns);
+    }
+}
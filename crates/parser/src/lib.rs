@@ -1,102 +1,287 @@
-use std::borrow::Cow;
+pub mod cst;
+pub use cst::{parse_cst, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, SyntaxTree};
+pub mod dispatch;
+pub use dispatch::{
+    dispatch_macro, register_dispatch_macro, unregister_dispatch_macro, DispatchMacro,
+};
 pub mod errors;
 pub use errors::{Caller, Error, Result};
+pub mod escape;
+pub use escape::{unescape_char, unescape_string, unescape_symbol};
+pub mod form;
+pub use form::Form;
 pub mod macros;
+pub mod number;
+pub use number::parse_number;
+pub mod reader;
+pub use reader::Reader;
+pub mod recover;
+pub use recover::parse_program_recovering;
+mod scan;
 pub mod test;
 
 pub mod source;
-use std::str::FromStr;
 
-use minilisp_data_structures::{Cell, Value};
+use minilisp_data_structures::{list, AsCell, Text, Value};
 use minilisp_util::unexpected;
 use pest::iterators::{Pair, Pairs};
 use pest::Parser;
 use pest_derive::Parser;
-pub use source::{Source, Span, SpanPosition};
+pub use source::{Source, SourceMap, Span, SpanPosition};
 pub const GRAMMAR: &'static str = include_str!("./grammar.pest");
 
 #[derive(Parser, Debug, Clone)]
 #[grammar = "src/grammar.pest"]
 pub struct MinilispSource;
 pub fn parse_source<'a>(input: &'a str) -> Result<'a, Value<'a>> {
-    let source_info = Source {
-        source: Cow::from(input),
-        filename: None,
-    };
-    let mut pairs = MinilispSource::parse(Rule::file, input).map_err(|e| {
-        Error::new(
-            e.variant.message().to_string(),
-            Some(Span::from_error(e, source_info.clone())),
-        )
-    })?;
-    let file = pairs.next().unwrap();
-    let nodes = (|pair: Pair<'a, Rule>| -> Option<Value<'a>> {
-        Some(pair_to_value(
-            pair.clone()
-                .into_inner()
-                .next()?
-                .into_inner()
-                .next()?,
-        ))
-    })(file)
-    .unwrap_or_default();
-    Ok(nodes)
+    Ok(parse_program(input)?
+        .into_iter()
+        .next()
+        .map(|form| form.value)
+        .unwrap_or_default())
 }
 
-pub fn map_pairs_to_list<'a>(pairs: Pairs<'a, Rule>) -> Value<'a> {
-    pairs.map(|pair| pair_to_value(pair)).collect()
+/// Parses every top-level form in `input`, in order, each paired
+/// with its source [`Span`].
+pub fn parse_program<'a>(input: &'a str) -> Result<'a, Vec<Form<'a>>> {
+    parse_program_with_filename(input, None)
 }
-pub fn pair_to_value<'a>(pair: Pair<'a, Rule>) -> Value<'a> {
-    match pair.as_rule() {
-        Rule::float =>
-            Value::float(f64::from_str(pair.as_span().as_str()).expect("float")),
-        Rule::integer =>
-            Value::integer(i64::from_str(pair.as_span().as_str()).expect("integer")),
-        Rule::string => Value::string(Cow::from(pair.as_span().as_str())),
-        Rule::symbol => Value::symbol(Cow::from(pair.as_span().as_str())),
-        Rule::quoted_symbol => {
-            let mut pairs = pair.clone().into_inner();
-            pairs.next().expect("quote");
-            let symbol = pairs.next().expect("symbol");
-            Value::quoted_symbol(symbol.as_span().as_str())
+
+/// Like [`parse_program`] but records `filename` in the [`Span`] of
+/// every form and error.
+pub fn parse_program_with_filename<'a>(
+    input: &'a str,
+    filename: Option<&'a str>,
+) -> Result<'a, Vec<Form<'a>>> {
+    parse_program_with_comments(input, filename).map(|(forms, _)| forms)
+}
+
+/// Like [`parse_program_with_filename`] but also returns the comments
+/// after the last form, which no form holds as trivia: every comment
+/// of a file that has no forms.
+pub fn parse_program_with_comments<'a>(
+    input: &'a str,
+    filename: Option<&'a str>,
+) -> Result<'a, (Vec<Form<'a>>, Vec<Span<'a>>)> {
+    let source_info = Source::new(input, filename);
+    let (forms, comments) =
+        read_forms(input, &source_info).map_err(|e| syntax_error(e, &source_info))?;
+    Ok((forms.into_iter().collect::<Result<'a, Vec<Form<'a>>>>()?, comments))
+}
+
+/// Each form read, or the error reading it, and the comments after
+/// the last form.
+pub(crate) type ReadForms<'a> = (Vec<Result<'a, Form<'a>>>, Vec<Span<'a>>);
+
+/// Parses `text` as a whole file and reads each of its top-level
+/// forms, with spans reported against `source`, along with the
+/// comments after the last form.
+///
+/// A syntax error fails the whole file, whereas an error reading a
+/// single form, such as an invalid escape sequence, only fails that
+/// form.
+pub(crate) fn read_forms<'a>(
+    text: &'a str,
+    source: &Source<'a>,
+) -> std::result::Result<ReadForms<'a>, pest::error::Error<Rule>> {
+    let file = MinilispSource::parse(Rule::file, text)?.next().unwrap();
+    let mut forms = Vec::<Result<'a, Form<'a>>>::new();
+    let mut comments = Vec::<Span<'a>>::new();
+    for pair in file.into_inner().flat_map(|pair| match pair.as_rule() {
+        Rule::statement => pair.into_inner().collect::<Vec<_>>(),
+        _ => vec![pair],
+    }) {
+        match pair.as_rule() {
+            Rule::COMMENT => comments.push(Span::from_pair(pair, source.clone())),
+            Rule::EOI => {},
+            _ => {
+                let mut spans = SourceMap::new();
+                forms.push(
+                    read_pair(pair.clone(), source, &mut spans)
+                        .map(|value| {
+                            Form::new(
+                                value,
+                                Span::from_pair(pair, source.clone())
+                                    .with_comments(std::mem::take(&mut comments)),
+                            )
+                            .with_spans(spans)
+                        })
+                        .map_err(|error| error.with_source(source.clone())),
+                )
+            },
+        }
+    }
+    Ok((forms, comments))
+}
+
+/// The list of `items`, ending in `dotted_tail` when improper.
+pub(crate) fn list_value<'a>(items: Vec<Value<'a>>, dotted_tail: Option<Value<'a>>) -> Value<'a> {
+    let value = Value::from_iter(items);
+    match dotted_tail {
+        Some(dotted_tail) => {
+            let mut cell = value.as_cell();
+            cell.set_dotted_tail(dotted_tail);
+            Value::list(cell)
         },
+        None => value,
+    }
+}
+
+/// The value read from `'datum`: the quoted variant of a symbol or a
+/// list, and `(quote datum)` for any other datum, including one that
+/// is quoted already.
+pub(crate) fn quoted_value<'a>(value: Value<'a>) -> Value<'a> {
+    match &value {
+        Value::Symbol(symbol) => Value::quoted_symbol(symbol),
+        Value::List(_) => value.quote(),
+        _ => list([Value::symbol("quote"), value]),
+    }
+}
+
+pub(crate) fn syntax_error<'a>(
+    error: pest::error::Error<Rule>,
+    source: &Source<'a>,
+) -> Error<'a> {
+    Error::new(
+        error.variant.message().to_string(),
+        Some(Span::from_error(error, source.clone())),
+    )
+}
+
+pub fn map_pairs_to_list<'a>(pairs: Pairs<'a, Rule>) -> Result<'a, Value<'a>> {
+    pairs.map(|pair| pair_to_value(pair)).collect()
+}
+pub fn pair_to_value<'a>(pair: Pair<'a, Rule>) -> Result<'a, Value<'a>> {
+    let source = Source::new(pair.get_input(), None);
+    read_pair(pair, &source, &mut SourceMap::new())
+}
+
+/// Converts `pair` to a [`Value`], recording in `spans` the span of
+/// every list read along the way and of its elements.
+fn read_pair<'a>(
+    pair: Pair<'a, Rule>,
+    source: &Source<'a>,
+    spans: &mut SourceMap<'a>,
+) -> Result<'a, Value<'a>> {
+    let value = read_pair_value(pair.clone(), source, spans)?;
+    if spans.get(&value).is_none() {
+        spans.insert(&value, datum_span(&pair, source), Vec::new());
+    }
+    Ok(value)
+}
+
+/// The span of the datum read from `pair`, named after its rule.
+fn datum_span<'a>(pair: &Pair<'a, Rule>, source: &Source<'a>) -> Span<'a> {
+    let mut pair = pair.clone();
+    while pair.as_rule() == Rule::value {
+        pair = pair.into_inner().next().expect("value");
+    }
+    let mut span = Span::from_pest_span(pair.as_span(), source.clone());
+    span.name = Some(format!("{:#?}", pair.as_rule()));
+    span
+}
+
+fn read_pair_value<'a>(
+    pair: Pair<'a, Rule>,
+    source: &Source<'a>,
+    spans: &mut SourceMap<'a>,
+) -> Result<'a, Value<'a>> {
+    Ok(match pair.as_rule() {
+        Rule::float | Rule::integer | Rule::radix_integer | Rule::rational =>
+            parse_number(&pair)?,
+        Rule::string => Value::String(Text::from(unescape_string(&pair)?)),
+        Rule::character => Value::char(unescape_char(&pair)?),
+        Rule::double_quoted_string =>
+            read_pair(pair.clone().into_inner().next().expect("string"), source, spans)?,
+        Rule::symbol => Value::symbol(unescape_symbol(pair.as_str())),
         Rule::t => Value::T,
-        Rule::unsigned => Value::unsigned_integer(
-            u32::from_str(pair.as_span().as_str()).expect("unsigned integer"),
-        ),
-        Rule::value => pair_to_value(pair.clone().into_inner().next().expect("value")),
+        Rule::value =>
+            read_pair(pair.clone().into_inner().next().expect("value"), source, spans)?,
         Rule::sexpr => {
-            let mut items = Cell::nil();
+            let mut items = Vec::new();
+            let mut elements = Vec::new();
             let mut pairs = pair.clone().into_inner();
-            let mut quoted = false;
+            let mut dotted_tail = None;
             loop {
                 if let Some(pair) = pairs.peek() {
                     if pair.as_rule() == Rule::close_paren {
                         break;
                     }
                 }
-                let pair = pairs.next().expect("quote, open_paren or item");
+                let pair = pairs.next().expect("open_paren or item");
                 match pair.as_rule() {
-                    Rule::quote => {
-                        quoted = true;
-                    },
+                    Rule::COMMENT => continue,
                     Rule::open_paren => continue,
                     Rule::close_paren => continue,
+                    Rule::dot => {
+                        let pair = pairs
+                            .find(|pair| pair.as_rule() != Rule::COMMENT)
+                            .expect("dotted tail");
+                        elements.push(datum_span(&pair, source));
+                        dotted_tail = Some(read_pair(pair, source, spans)?);
+                    },
                     _ => {
-                        items.push_value(pair_to_value(pair));
+                        elements.push(datum_span(&pair, source));
+                        items.push(read_pair(pair, source, spans)?);
                         continue;
                     },
                 }
             }
             pairs.next().expect("close_paren");
-            let value = Value::from_iter(items.into_iter());
-            if quoted {
-                value.quote()
-            } else {
-                value
-            }
+            let value = list_value(items, dotted_tail);
+            spans.insert(&value, datum_span(&pair, source), elements);
+            value
+        },
+        Rule::vector => Value::vector(
+            pair.clone()
+                .into_inner()
+                .filter(|pair| {
+                    !matches!(
+                        pair.as_rule(),
+                        Rule::open_bracket | Rule::close_bracket | Rule::COMMENT
+                    )
+                })
+                .map(|pair| read_pair(pair, source, spans))
+                .collect::<Result<'a, Vec<Value<'a>>>>()?,
+        ),
+        Rule::quoted => {
+            let datum = pair
+                .clone()
+                .into_inner()
+                .find(|pair| !matches!(pair.as_rule(), Rule::quote | Rule::COMMENT))
+                .expect("datum");
+            let value = quoted_value(read_pair(datum, source, spans)?);
+            spans.set_span(&value, datum_span(&pair, source));
+            value
+        },
+        Rule::quasiquote | Rule::unquote | Rule::unquote_splicing => {
+            let symbol = match pair.as_rule() {
+                Rule::quasiquote => "backquote",
+                Rule::unquote => ",",
+                _ => ",@",
+            };
+            let mut pairs = pair
+                .clone()
+                .into_inner()
+                .filter(|pair| pair.as_rule() != Rule::COMMENT);
+            let prefix = pairs.next().expect("prefix");
+            let datum = pairs.next().expect("datum");
+            let elements = vec![datum_span(&prefix, source), datum_span(&datum, source)];
+            let value = list([Value::symbol(symbol), read_pair(datum, source, spans)?]);
+            spans.insert(&value, datum_span(&pair, source), elements);
+            value
+        },
+        Rule::dispatch => {
+            let mut pairs = pair
+                .clone()
+                .into_inner()
+                .filter(|pair| pair.as_rule() != Rule::COMMENT);
+            let prefix = pairs.next().expect("dispatch_prefix");
+            let datum = read_pair(pairs.next().expect("datum"), source, spans)?;
+            dispatch::expand(prefix.as_str(), datum)
+                .map_err(|error| error.or_span(Span::from_pair(pair, source.clone())))?
         },
         Rule::nil => Value::nil(),
         _ => unexpected!(pair),
-    }
+    })
 }
@@ -0,0 +1,112 @@
+use std::num::IntErrorKind;
+use std::str::FromStr;
+
+use minilisp_data_structures::{BigInteger, Number, Rational, Value};
+use pest::iterators::Pair;
+
+use crate::{Error, Result, Rule, Source, Span};
+
+/// Converts an `integer`, `radix_integer`, `rational` or `float` pair
+/// to a [`Value`].
+///
+/// Integers that fit in a `u32` become [`Value::UnsignedInteger`],
+/// negative or larger ones become [`Value::Integer`], and integers
+/// beyond the range of `i64` become [`Value::BigInteger`]. Radix
+/// integers are written `#xFF`, `#o17`, `#b101` or `#24r1k`;
+/// rationals `1/3` are reduced to lowest terms, to an integer when
+/// the denominator divides the numerator, and a zero denominator is
+/// reported as an [`Error`] spanning the literal; floats accept an
+/// exponent as well as `1.0e+INF` and `0.0e+NaN`.
+pub fn parse_number<'a>(pair: &Pair<'a, Rule>) -> Result<'a, Value<'a>> {
+    let literal = pair.as_str();
+    match pair.as_rule() {
+        Rule::float => Ok(Value::float(parse_float(literal))),
+        Rule::integer => integer_value(pair, literal.trim_end_matches('.'), 10),
+        Rule::rational => {
+            let (numerator, denominator) = literal.split_once('/').expect("rational");
+            let numerator = BigInteger::from_str(numerator).expect("digits");
+            let denominator = BigInteger::from_str(denominator).expect("digits");
+            if denominator.is_zero() {
+                return Err(error(
+                    pair,
+                    format!("zero denominator in rational literal {:#?}", literal),
+                ));
+            }
+            Ok(Value::from(Number::from_rational(Rational::new(numerator, denominator))))
+        },
+        Rule::radix_integer => {
+            let (radix, digits) = match literal[1..2].to_ascii_lowercase().as_str() {
+                "x" => (16, &literal[2..]),
+                "o" => (8, &literal[2..]),
+                "b" => (2, &literal[2..]),
+                _ => {
+                    let (radix, digits) = literal[1..]
+                        .split_once(['r', 'R'])
+                        .expect("radix integer");
+                    match u32::from_str(radix) {
+                        Ok(radix) if (2..=36).contains(&radix) => (radix, digits),
+                        _ =>
+                            return Err(error(
+                                pair,
+                                format!("invalid radix in integer literal {:#?}", literal),
+                            )),
+                    }
+                },
+            };
+            integer_value(pair, digits, radix)
+        },
+        _ => Err(error(pair, format!("not a number: {:#?}", literal))),
+    }
+}
+
+fn parse_float(literal: &str) -> f64 {
+    let negative = literal.starts_with('-');
+    let value = if literal.ends_with("INF") {
+        f64::INFINITY
+    } else if literal.ends_with("NaN") {
+        f64::NAN
+    } else {
+        return f64::from_str(literal).expect("float");
+    };
+    if negative {
+        -value
+    } else {
+        value
+    }
+}
+
+fn integer_value<'a>(
+    pair: &Pair<'a, Rule>,
+    digits: &str,
+    radix: u32,
+) -> Result<'a, Value<'a>> {
+    match i64::from_str_radix(digits, radix) {
+        Ok(integer) => Ok(match u32::try_from(integer) {
+            Ok(unsigned) if !digits.starts_with('-') => Value::unsigned_integer(unsigned),
+            _ => Value::integer(integer),
+        }),
+        Err(e)
+            if matches!(e.kind(), IntErrorKind::PosOverflow | IntErrorKind::NegOverflow) =>
+            Ok(Value::BigInteger(
+                BigInteger::from_str_radix(digits, radix).expect("digits"),
+            )),
+        Err(_) => Err(error(
+            pair,
+            format!(
+                "invalid digit in base {} integer literal {:#?}",
+                radix,
+                pair.as_str()
+            ),
+        )),
+    }
+}
+
+fn error<'a>(pair: &Pair<'a, Rule>, message: String) -> Error<'a> {
+    Error::new(
+        message,
+        Some(Span::from_pest_span(
+            pair.as_span(),
+            Source::new(pair.get_input(), None),
+        )),
+    )
+}
@@ -0,0 +1,172 @@
+use std::io::{BufRead, BufReader, Read};
+
+use crate::recover::region_span;
+use crate::scan::Scanner;
+use crate::{read_forms, syntax_error, Error, Form, Result, Source};
+
+/// Reads top-level forms one at a time from any [`BufRead`], such
+/// as stdin, a pipe or a file.
+///
+/// Input is pulled a line at a time and only until the next form is
+/// complete, so forms are yielded as the input arrives. Each line is
+/// scanned once and the reader holds no more than the lines of the
+/// forms not read yet. Forms are copied out of the input with
+/// [`Form::into_owned`], so they borrow nothing and can be kept for
+/// as long as needed.
+///
+/// Spans report lines and columns within the whole input.
+pub struct Reader<R> {
+    input: R,
+    filename: Option<String>,
+    /// input from the start of line `line` on
+    buffer: String,
+    scanner: Scanner,
+    /// end of the last form read from `buffer`
+    consumed: usize,
+    /// start of the line of `consumed` within `buffer`
+    line_start: usize,
+    line: usize,
+    /// characters between `line_start` and `consumed`
+    column: usize,
+    eof: bool,
+}
+
+impl<R: Read> Reader<BufReader<R>> {
+    pub fn from_read(input: R) -> Reader<BufReader<R>> {
+        Reader::new(BufReader::new(input))
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    pub fn new(input: R) -> Reader<R> {
+        Reader {
+            input,
+            filename: None,
+            buffer: String::new(),
+            scanner: Scanner::new(false),
+            consumed: 0,
+            line_start: 0,
+            line: 1,
+            column: 0,
+            eof: false,
+        }
+    }
+
+    /// Records `filename` in the [`Span`](crate::Span) of every form
+    /// and error.
+    pub fn with_filename(mut self, filename: &str) -> Reader<R> {
+        self.filename = Some(filename.to_string());
+        self
+    }
+
+    /// Reads the next top-level form, or `None` at the end of the
+    /// input.
+    ///
+    /// An error only fails the form it occurs in, the next call reads
+    /// on from the form after it.
+    pub fn read_form<'o>(&mut self) -> Result<'o, Option<Form<'o>>> {
+        loop {
+            self.scanner.scan(&self.buffer, self.eof);
+            match self.scanner.next_item() {
+                Some((item, true)) => return self.read_chunk(item.end).map(Some),
+                Some((item, false)) => {
+                    let end = self.buffer.len();
+                    let source = self.source(end);
+                    let (lines, offset) = (self.line - 1, self.line_start);
+                    let error = Error::new(
+                        "unbalanced parentheses: list is never closed",
+                        Some(region_span(
+                            &self.buffer[offset..],
+                            item.start - offset..item.end - offset,
+                            &source,
+                        )),
+                    )
+                    .shift(lines, 0)
+                    .into_owned();
+                    self.consume(end);
+                    return Err(error);
+                },
+                None if self.eof => return Ok(None),
+                None => {},
+            }
+            match self.input.read_line(&mut self.buffer) {
+                Ok(0) => self.eof = true,
+                Ok(_) => {},
+                Err(error) => {
+                    self.eof = true;
+                    return Err(Error::new(error, None));
+                },
+            }
+        }
+    }
+
+    /// Line number of the next line to be read into a form.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Reads the single form ending at byte `end` of the buffer, along
+    /// with the comments before it, consuming it even when it fails to
+    /// read.
+    fn read_chunk<'o>(&mut self, end: usize) -> Result<'o, Form<'o>> {
+        let source = self.source(end);
+        let chunk = &self.buffer[self.consumed..end];
+        let (lines, columns) = (self.line - 1, self.column);
+        let form = read_forms(chunk, &source)
+            .map_err(|error| syntax_error(error, &source))
+            .and_then(|(forms, _)| {
+                forms
+                    .into_iter()
+                    .next()
+                    .unwrap_or_else(|| {
+                        Err(Error::new("end of file during parsing", None))
+                    })
+            })
+            .map(|mut form| {
+                form.shift(lines, columns);
+                form.into_owned()
+            })
+            .map_err(|error| error.shift(lines, columns).into_owned());
+        self.consume(end);
+        form
+    }
+
+    /// The lines of the buffer up to byte `end` that hold forms not
+    /// read yet.
+    fn source(&self, end: usize) -> Source<'_> {
+        let mut source =
+            Source::new(&self.buffer[self.line_start..end], self.filename.as_deref());
+        source.first_line = self.line;
+        source
+    }
+
+    /// Marks the buffer as read up to byte `end`, dropping the lines
+    /// read once they make up most of it.
+    fn consume(&mut self, end: usize) {
+        for (index, c) in self.buffer[self.consumed..end].char_indices() {
+            if c == '\n' {
+                self.line += 1;
+                self.line_start = self.consumed + index + 1;
+                self.column = 0;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.consumed = end;
+        if self.line_start > self.buffer.len() / 2 {
+            let count = self.line_start;
+            self.buffer.drain(..count);
+            self.scanner.drop_start(count);
+            self.consumed -= count;
+            self.line_start = 0;
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<'static, Form<'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_form().transpose()
+    }
+}
@@ -0,0 +1,100 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use pest::Position;
+
+use crate::scan::top_level_items;
+use crate::{read_forms, syntax_error, Error, Form, Source, Span, SpanPosition};
+
+/// Parses every top-level form in `input` like [`parse_program`],
+/// but recovers from errors instead of stopping at the first one.
+///
+/// Each syntax error is recorded and the top-level form it occurred
+/// in is skipped: a form runs up to its matching close paren or, when
+/// unbalanced, up to the next line starting with an open paren; stray
+/// close parens are skipped on their own. Forms that parse but cannot
+/// be read, e.g. because of an invalid escape sequence, are skipped
+/// too. Returns the forms that could be read and the errors, both in
+/// source order.
+///
+/// [`parse_program`]: crate::parse_program
+pub fn parse_program_recovering<'a>(
+    input: &'a str,
+    filename: Option<&'a str>,
+) -> (Vec<Form<'a>>, Vec<Error<'a>>) {
+    let source = Source::new(input, filename);
+    let mut forms = Vec::<Form<'a>>::new();
+    let mut errors = Vec::<Error<'a>>::new();
+    if let Ok((results, _)) = read_forms(input, &source) {
+        for result in results {
+            match result {
+                Ok(form) => forms.push(form),
+                Err(error) => errors.push(error),
+            }
+        }
+        return (forms, errors);
+    }
+    // each top-level item is parsed on its own, along with the
+    // comments before it, so that an error only skips the item it
+    // occurred in and nothing is parsed twice
+    let mut start = 0;
+    let mut position = SpanPosition { line: 1, column: 1 };
+    for (item, closed) in top_level_items(input, true) {
+        let text = &input[start..item.end];
+        let (lines, columns) = (position.line - 1, position.column - 1);
+        advance(&mut position, text);
+        start = item.end;
+        if !closed {
+            errors.push(Error::new(
+                "unbalanced parentheses: list is never closed",
+                Some(region_span(input, item, &source)),
+            ));
+            continue;
+        }
+        match read_forms(text, &source) {
+            Ok((results, _)) =>
+                for result in results {
+                    match result {
+                        Ok(mut form) => {
+                            form.shift(lines, columns);
+                            forms.push(form);
+                        },
+                        Err(error) => errors.push(error.shift(lines, columns)),
+                    }
+                },
+            Err(error) => errors.push(syntax_error(error, &source).shift(lines, columns)),
+        }
+    }
+    errors.sort_by_key(|error| error.span().map(|span| span.start_pos));
+    (forms, errors)
+}
+
+/// Moves `position` past `text`.
+fn advance(position: &mut SpanPosition, text: &str) {
+    for c in text.chars() {
+        if c == '\n' {
+            position.line += 1;
+            position.column = 1;
+        } else {
+            position.column += 1;
+        }
+    }
+}
+
+pub(crate) fn region_span<'a>(
+    text: &str,
+    region: Range<usize>,
+    source: &Source<'a>,
+) -> Span<'a> {
+    let start = Position::new(text, region.start).expect("region within text");
+    let end = Position::new(text, region.end).expect("region within text");
+    Span {
+        input: Cow::from(start.line_of().to_string()),
+        name: None,
+        start_pos: SpanPosition::from_pest(start),
+        end_pos: SpanPosition::from_pest(end),
+        source: source.clone(),
+        inner: None,
+        comments: Vec::new(),
+    }
+}
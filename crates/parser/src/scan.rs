@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+
+/// Byte ranges of the top-level items of `text`, skipping whitespace
+/// and comments, each paired with `false` for lists that are never
+/// closed. A stray close paren is an item of its own.
+///
+/// With `split_unclosed`, a list that is never closed ends before the
+/// next line that starts with an open paren; otherwise it runs to the
+/// end of `text`.
+pub(crate) fn top_level_items(
+    text: &str,
+    split_unclosed: bool,
+) -> Vec<(Range<usize>, bool)> {
+    let mut scanner = Scanner::new(split_unclosed);
+    scanner.scan(text, true);
+    scanner.items.into()
+}
+
+/// Finds the top-level items of a text that grows at its end, like
+/// [`top_level_items`], scanning each part of the text once.
+///
+/// A token that runs up to the end of the text may go on in the text
+/// yet to come, so it is scanned again once there is more of it.
+#[derive(Clone, Debug)]
+pub(crate) struct Scanner {
+    split_unclosed: bool,
+    state: State,
+    items: VecDeque<(Range<usize>, bool)>,
+}
+
+/// Where the scan of a text stopped, between two tokens.
+#[derive(Clone, Copy, Debug)]
+struct State {
+    offset: usize,
+    depth: usize,
+    /// start of the list being scanned while `depth > 0`
+    start: usize,
+    /// start of the quotes or dispatch prefix before the next datum
+    prefix: Option<usize>,
+    line_start: bool,
+}
+
+impl Scanner {
+    pub(crate) fn new(split_unclosed: bool) -> Scanner {
+        Scanner {
+            split_unclosed,
+            state: State {
+                offset: 0,
+                depth: 0,
+                start: 0,
+                prefix: None,
+                line_start: true,
+            },
+            items: VecDeque::new(),
+        }
+    }
+
+    /// Scans `text` from where the last scan stopped. `text` must
+    /// start with the text scanned before. With `complete`, `text`
+    /// is all there is, and a list still open is an item that is
+    /// never closed.
+    pub(crate) fn scan(&mut self, text: &str, complete: bool) {
+        let split_unclosed = self.split_unclosed;
+        let mut state = self.state;
+        let mut items = Vec::<(Range<usize>, bool)>::new();
+        let offset = state.offset;
+        let mut chars = text[offset..]
+            .char_indices()
+            .map(|(index, c)| (index + offset, c))
+            .peekable();
+        while let Some((index, c)) = chars.next() {
+            let at_line_start = std::mem::replace(&mut state.line_start, c == '\n');
+            if split_unclosed && state.depth > 0 && at_line_start && c == '(' {
+                items.push((state.start..index, false));
+                state.depth = 0;
+            }
+            let item_start = if state.depth == 0 {
+                state.prefix.take().unwrap_or(index)
+            } else {
+                index
+            };
+            match c {
+                ';' => {
+                    while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+                    state.prefix = None;
+                },
+                '#' if chars.peek().map(|(_, c)| *c) == Some(';') => {
+                    // a datum comment goes with the datum it comments out
+                    chars.next();
+                    if state.depth == 0 {
+                        state.prefix = Some(item_start);
+                    }
+                },
+                '#' if chars.peek().map(|(_, c)| *c) == Some('|') => {
+                    chars.next();
+                    let mut nesting = 1;
+                    while let Some((_, c)) = chars.next() {
+                        match (c, chars.peek().map(|(_, c)| *c)) {
+                            ('|', Some('#')) => {
+                                chars.next();
+                                nesting -= 1;
+                                if nesting == 0 {
+                                    break;
+                                }
+                            },
+                            ('#', Some('|')) => {
+                                chars.next();
+                                nesting += 1;
+                            },
+                            _ => {},
+                        }
+                    }
+                },
+                '\'' | '`' | ',' =>
+                    if state.depth == 0 {
+                        state.prefix = Some(item_start);
+                    },
+                '(' | '[' => {
+                    if state.depth == 0 {
+                        state.start = item_start;
+                    }
+                    state.depth += 1;
+                },
+                ')' | ']' =>
+                    if state.depth == 0 {
+                        items.push((item_start..index + 1, true));
+                    } else {
+                        state.depth -= 1;
+                        if state.depth == 0 {
+                            items.push((state.start..index + 1, true));
+                        }
+                    },
+                c if c.is_whitespace() => {},
+                c => {
+                    let mut end = index + c.len_utf8();
+                    match c {
+                        '"' => {
+                            while let Some((index, c)) = chars.next() {
+                                end = index + c.len_utf8();
+                                match c {
+                                    '\\' => {
+                                        if let Some((index, c)) = chars.next() {
+                                            end = index + c.len_utf8();
+                                        }
+                                    },
+                                    '"' => break,
+                                    _ => {},
+                                }
+                            }
+                        },
+                        '?' | '\\' =>
+                            if let Some((index, c)) = chars.next() {
+                                end = index + c.len_utf8();
+                                if c == '\\' {
+                                    if let Some((index, c)) = chars.next() {
+                                        end = index + c.len_utf8();
+                                    }
+                                }
+                            },
+                        _ => {},
+                    }
+                    while let Some((index, c)) =
+                        chars.next_if(|(_, c)| !is_delimiter(*c))
+                    {
+                        end = index + c.len_utf8();
+                        if c == '\\' {
+                            if let Some((index, c)) = chars.next() {
+                                end = index + c.len_utf8();
+                            }
+                        }
+                    }
+                    let dispatch = text[index..end].starts_with('#')
+                        && matches!(chars.peek(), Some((_, '(' | '[' | '"' | '\'')));
+                    if state.depth == 0 {
+                        if dispatch {
+                            state.prefix = Some(item_start);
+                        } else {
+                            items.push((item_start..end, true));
+                        }
+                    }
+                },
+            }
+            match chars.peek() {
+                None if !complete => break,
+                next => {
+                    state.offset = next.map(|(index, _)| *index).unwrap_or(text.len());
+                    self.state = state;
+                    self.items.extend(items.drain(..));
+                },
+            }
+        }
+        if complete && self.state.depth > 0 {
+            self.items.push_back((self.state.start..text.len(), false));
+            self.state.depth = 0;
+        }
+    }
+
+    /// Takes the first item found and not taken yet.
+    pub(crate) fn next_item(&mut self) -> Option<(Range<usize>, bool)> {
+        self.items.pop_front()
+    }
+
+    /// Moves the scan back `count` bytes, after as many bytes, all
+    /// of them before the items not taken yet, were dropped from the
+    /// start of the text.
+    pub(crate) fn drop_start(&mut self, count: usize) {
+        self.state.offset -= count;
+        self.state.start = self.state.start.saturating_sub(count);
+        self.state.prefix = self.state.prefix.map(|prefix| prefix - count);
+        for (item, _) in self.items.iter_mut() {
+            *item = item.start - count..item.end - count;
+        }
+    }
+}
+
+pub(crate) fn is_delimiter(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(c, '(' | ')' | '[' | ']' | '"' | ';' | '\'' | '`' | ',')
+}
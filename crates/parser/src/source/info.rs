@@ -1,30 +1,47 @@
 use std::borrow::Cow;
 
+use minilisp_data_structures::Text;
+
 
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 pub struct Source<'a> {
-    pub source: Cow<'a, str>,
+    pub source: Text<'a>,
     pub filename: Option<Cow<'a, str>>,
+    /// Line number of the first line of `source`, greater than 1 when
+    /// `source` is a chunk read from further into a stream.
+    pub first_line: usize,
 }
 impl<'a> Source<'a> {
     pub fn new(source: &'a str, filename: Option<&'a str>) -> Source<'a> {
         Source {
-            source: Cow::from(source),
-            filename: filename.map(|filename| Cow::from(filename))
+            source: Text::from(source),
+            filename: filename.map(Cow::from),
+            first_line: 1,
         }
     }
 
     pub fn without_filename<T: std::fmt::Display>(source: T) -> Source<'a> {
         Source {
-            source: Cow::from(source.to_string()),
+            source: Text::new(source),
             filename: None,
+            first_line: 1,
         }
     }
 
     pub fn filename(&self) -> Option<String> {
         self.filename.clone().map(String::from)
     }
+
+    /// Copies this source into one that borrows nothing. Clones of the
+    /// copy share its text.
+    pub fn to_owned<'o>(&self) -> Source<'o> {
+        Source {
+            source: self.source.to_owned(),
+            filename: self.filename.clone().map(|filename| Cow::Owned(filename.into_owned())),
+            first_line: self.first_line,
+        }
+    }
 }
 
 impl<'a> From<&'a str> for Source<'a> {
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use minilisp_data_structures::{Cell, Quotable, Value, WeakCell};
+
+use crate::{Source, Span};
+
+/// Spans of a list and of each of its elements.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Entry<'a> {
+    list: WeakCell<'a>,
+    span: Span<'a>,
+    /// Spans of the elements in order, followed by the span of the
+    /// dotted tail of an improper list.
+    elements: Vec<Span<'a>>,
+}
+
+/// Side table from every list read by the parser, and from each of
+/// its elements, to the [`Span`] it was read from.
+///
+/// Lists are identified by their first cons cell rather than compared
+/// for equality, so equal lists read from different places keep
+/// their own spans, and atoms, which carry no identity, are found as
+/// elements of the list holding them. The map does not keep lists
+/// alive: [`SourceMap::prune`] drops the spans of lists that were
+/// dropped since.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct SourceMap<'a> {
+    entries: HashMap<*const (), Entry<'a>>,
+}
+impl<'a> SourceMap<'a> {
+    pub fn new() -> SourceMap<'a> {
+        SourceMap::default()
+    }
+
+    /// Records the span of `list` and the spans of its `elements`,
+    /// the dotted tail last. Values other than non-empty lists have
+    /// no identity and are not recorded.
+    pub fn insert(&mut self, list: &Value<'a>, span: Span<'a>, elements: Vec<Span<'a>>) {
+        let cell = match list {
+            Value::List(cell) | Value::QuotedList(cell) => cell,
+            _ => return,
+        };
+        if let (Some(address), Some(weak)) = (cell.as_ptr(), cell.downgrade()) {
+            self.entries.insert(
+                address,
+                Entry {
+                    list: weak,
+                    span,
+                    elements,
+                },
+            );
+        }
+    }
+
+    /// Replaces the span of `list`, keeping the spans of its elements,
+    /// or records it with no elements when `list` was not recorded.
+    pub(crate) fn set_span(&mut self, list: &Value<'a>, span: Span<'a>) {
+        let elements = self.entry(list).map(|entry| entry.elements.clone());
+        self.insert(list, span, elements.unwrap_or_default());
+    }
+
+    pub fn extend(&mut self, other: SourceMap<'a>) {
+        self.entries.extend(other.entries);
+    }
+
+    /// The span `list` was read from.
+    ///
+    /// `O(1)`
+    pub fn get(&self, list: &Value<'a>) -> Option<&Span<'a>> {
+        self.entry(list).map(|entry| &entry.span)
+    }
+
+    /// The span of the element at `index` of `list`, where the index
+    /// after the last element is that of the dotted tail.
+    ///
+    /// `O(1)`
+    pub fn get_element(&self, list: &Value<'a>, index: usize) -> Option<&Span<'a>> {
+        self.entry(list)?.elements.get(index)
+    }
+
+    /// The span of `value` as read within `list`: its own span when it
+    /// is a list, otherwise that of the first element of `list` equal
+    /// to it.
+    ///
+    /// `O(n)` in the length of `list`
+    pub fn get_in(&self, value: &Value<'a>, list: &Value<'a>) -> Option<&Span<'a>> {
+        if let Some(span) = self.get(value) {
+            return Some(span);
+        }
+        let cell = match list {
+            Value::List(cell) | Value::QuotedList(cell) => cell,
+            _ => return None,
+        };
+        let index = cell
+            .values()
+            .into_iter()
+            .chain(cell.dotted_tail())
+            .position(|element| element == *value)?;
+        self.get_element(list, index)
+    }
+
+    /// Drops the spans of the lists that are no longer alive.
+    pub fn prune(&mut self) {
+        self.entries.retain(|_, entry| entry.list.is_alive());
+    }
+
+    /// Copies `value` like [`Value::to_owned`], recording in `owned`
+    /// the spans of each of its lists against their copies, with
+    /// `source` in place of the source they were read from.
+    pub(crate) fn to_owned_in<'o>(
+        &self,
+        value: &Value<'a>,
+        source: &Source<'o>,
+        owned: &mut SourceMap<'o>,
+    ) -> Value<'o> {
+        let copy = match value {
+            Value::List(cell) | Value::QuotedList(cell) => {
+                let mut copy = Cell::from_iter(
+                    cell.values()
+                        .iter()
+                        .map(|item| self.to_owned_in(item, source, owned)),
+                );
+                if let Some(dotted_tail) = cell.dotted_tail() {
+                    copy.set_dotted_tail(self.to_owned_in(&dotted_tail, source, owned));
+                }
+                copy.set_quoted(cell.is_quoted());
+                match value {
+                    Value::QuotedList(_) => Value::QuotedList(copy),
+                    _ => Value::List(copy),
+                }
+            },
+            Value::Vector(vector) => Value::vector(
+                vector
+                    .values()
+                    .iter()
+                    .map(|item| self.to_owned_in(item, source, owned)),
+            ),
+            value => return value.to_owned(),
+        };
+        if let Some(entry) = self.entry(value) {
+            owned.insert(
+                &copy,
+                entry.span.clone().into_owned(source),
+                entry
+                    .elements
+                    .iter()
+                    .map(|span| span.clone().into_owned(source))
+                    .collect(),
+            );
+        }
+        copy
+    }
+
+    fn entry(&self, list: &Value<'a>) -> Option<&Entry<'a>> {
+        match list {
+            Value::List(cell) | Value::QuotedList(cell) => self.entries.get(&cell.as_ptr()?),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn shift(&mut self, lines: usize, columns: usize) {
+        for entry in self.entries.values_mut() {
+            entry.span.shift(lines, columns);
+            for span in entry.elements.iter_mut() {
+                span.shift(lines, columns);
+            }
+        }
+    }
+
+    /// How many lists have spans.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Spans of the lists, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Span<'a>> {
+        self.entries.values().map(|entry| &entry.span)
+    }
+}
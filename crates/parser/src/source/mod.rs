@@ -1,6 +1,8 @@
 pub mod info;
+pub mod map;
 pub mod span;
 pub mod position;
 pub use info::Source;
+pub use map::SourceMap;
 pub use span::Span;
 pub use position::SpanPosition;
@@ -14,6 +14,8 @@ pub struct Span<'a> {
     pub end_pos: SpanPosition,
     pub source: Source<'a>,
     pub inner: Option<Vec<Span<'a>>>,
+    /// Comments immediately preceding this span, kept as trivia.
+    pub comments: Vec<Span<'a>>,
 }
 impl<'a> Span<'a> {
     pub fn from_pair(pair: Pair<'a, Rule>, source: Source<'a>) -> Span<'a> {
@@ -32,18 +34,26 @@ impl<'a> Span<'a> {
                 if inner.peek().is_none() {
                     None
                 } else {
+                    let mut comments = Vec::<Span<'a>>::new();
                     Some(
                         inner
                             .map(|pair| {
-                                Span::from_pair(
+                                let mut span = Span::from_pair(
                                     pair.clone(),
                                     source.clone(),
-                                )
+                                );
+                                if pair.as_rule() == Rule::COMMENT {
+                                    comments.push(span.clone());
+                                } else {
+                                    span.comments = std::mem::take(&mut comments);
+                                }
+                                span
                             })
                             .collect(),
                     )
                 }
             },
+            comments: Vec::new(),
         }
     }
 
@@ -63,6 +73,19 @@ impl<'a> Span<'a> {
             end_pos,
             source: source,
             inner: None,
+            comments: Vec::new(),
+        }
+    }
+
+    pub fn from_pest_span(span: pest::Span<'a>, source: Source<'a>) -> Span<'a> {
+        Span {
+            input: Cow::from(span.start_pos().line_of()),
+            name: None,
+            start_pos: SpanPosition::from_pest(span.start_pos()),
+            end_pos: SpanPosition::from_pest(span.end_pos()),
+            source,
+            inner: None,
+            comments: Vec::new(),
         }
     }
 
@@ -82,6 +105,20 @@ impl<'a> Span<'a> {
         self.source.filename()
     }
 
+    pub fn comments(&self) -> Vec<Span<'a>> {
+        self.comments.clone()
+    }
+
+    pub fn is_comment(&self) -> bool {
+        self.name.as_deref() == Some("COMMENT")
+    }
+
+    pub fn with_comments(&self, comments: Vec<Span<'a>>) -> Span<'a> {
+        let mut info = self.clone();
+        info.comments = comments;
+        info
+    }
+
     pub fn with_input(&self, input: &'a str) -> Span<'a> {
         let mut info = self.clone();
         info.input = Cow::from(input);
@@ -100,53 +137,118 @@ impl<'a> Span<'a> {
         self.end_pos.to_tuple()
     }
 
+    /// `file:line:column` of the start of this span, with `<input>`
+    /// standing in for sources without a filename.
+    pub fn location(&self) -> String {
+        format!(
+            "{}:{}:{}",
+            self.filename().unwrap_or_else(|| "<input>".to_string()),
+            self.start_pos.line,
+            self.start_pos.column
+        )
+    }
+
+    /// Moves this span, its inner spans and its comments `lines`
+    /// lines further down and, on the first line, `columns` columns
+    /// further right, e.g. when it was read from a chunk that starts
+    /// further into a larger input.
+    pub(crate) fn shift(&mut self, lines: usize, columns: usize) {
+        for position in [&mut self.start_pos, &mut self.end_pos] {
+            if position.line == 1 {
+                position.column += columns;
+            }
+            position.line += lines;
+        }
+        for span in self.inner.iter_mut().flatten().chain(self.comments.iter_mut()) {
+            span.shift(lines, columns);
+        }
+    }
+
+    /// Copies this span, its inner spans and its comments into spans
+    /// that borrow nothing, all of them sharing `source`, an owned
+    /// copy of the source they were read from.
+    pub(crate) fn into_owned<'o>(self, source: &Source<'o>) -> Span<'o> {
+        Span {
+            input: Cow::Owned(self.input.into_owned()),
+            name: self.name,
+            start_pos: self.start_pos,
+            end_pos: self.end_pos,
+            source: source.clone(),
+            inner: self.inner.map(|spans| {
+                spans.into_iter().map(|span| span.into_owned(source)).collect()
+            }),
+            comments: self
+                .comments
+                .into_iter()
+                .map(|span| span.into_owned(source))
+                .collect(),
+        }
+    }
+
+    /// `true` when `other` starts and ends within this span.
+    pub fn encloses(&self, other: &Span<'a>) -> bool {
+        self.source == other.source
+            && self.start_pos <= other.start_pos
+            && other.end_pos <= self.end_pos
+    }
+
     pub fn highlight_input(&self, indent: usize) -> String {
         minilisp_util::color::fore(self.highlight_input_chars(indent), 32)
     }
 
+    /// Renders the location followed by the source lines covered by
+    /// this span, numbered as in the source, with the spanned
+    /// characters highlighted.
     fn highlight_input_chars(&self, indent: usize) -> String {
         let start_pos = self.start_pos.clone();
-        let end_pos = self.end_pos.clone();
-        self.input
-            .lines()
-            .enumerate()
-            .map(|(no, line)| {
-                (
-                    no + 1,
-                    line.chars()
+        let end_pos = if self.end_pos > self.start_pos {
+            self.end_pos.clone()
+        } else {
+            SpanPosition {
+                line: start_pos.line,
+                column: start_pos.column + 1,
+            }
+        };
+        let lines = if self.source.source.is_empty() {
+            self.input
+                .lines()
+                .enumerate()
+                .map(|(no, line)| (start_pos.line + no, line))
+                .collect::<Vec<(usize, &str)>>()
+        } else {
+            self.source
+                .source
+                .lines()
+                .enumerate()
+                .map(|(no, line)| (no + self.source.first_line, line))
+                .filter(|(no, _)| (start_pos.line..=end_pos.line).contains(no))
+                .collect::<Vec<(usize, &str)>>()
+        };
+        let width = end_pos.line.to_string().len();
+        let mut rendered = vec![format!("{}--> {}", " ".repeat(indent), self.location())];
+        for (line, text) in lines {
+            rendered.push(minilisp_util::color::bg(
+                format!(
+                    "{}{:>width$} | {}",
+                    " ".repeat(indent),
+                    line,
+                    text.chars()
                         .enumerate()
-                        .map(|(no, column)| (no + 1, column.to_string()))
-                        .collect::<Vec<(usize, String)>>(),
-                )
-            })
-            .map(|(line, columns)| {
-                minilisp_util::color::bg(
-                    format!(
-                        "{}{}",
-                        " ".repeat(indent),
-                        columns
-                            .iter()
-                            .map(|(column, text)| {
-                                let column = column.clone();
-                                if line == start_pos.line && column == start_pos.column {
-                                    minilisp_util::color::bgfg(text, 235, 198)
-                                } else if line == end_pos.line && column == end_pos.column {
-                                    [
-                                        minilisp_util::color::reset(""),
-                                        minilisp_util::color::bg(text, 235),
-                                    ]
-                                    .join("")
-                                } else {
-                                    minilisp_util::color::bg(text, 235)
-                                }
-                            })
-                            .collect::<String>()
-                    ),
-                    235,
-                )
-            })
-            .collect::<Vec<String>>()
-            .join("\n")
+                        .map(|(column, text)| {
+                            let position = SpanPosition { line, column: column + 1 };
+                            if start_pos <= position && position < end_pos {
+                                minilisp_util::color::bgfg(text.to_string(), 235, 198)
+                            } else {
+                                minilisp_util::color::bg(text.to_string(), 235)
+                            }
+                        })
+                        .collect::<String>(),
+                    width = width
+                ),
+                235,
+            ));
+        }
+        rendered.join("\n")
     }
 }
 
@@ -1,5 +1,7 @@
 use std::borrow::Cow;
 
+use minilisp_data_structures::Text;
+
 use crate::{Source, Span, SpanPosition};
 
 #[macro_export]
@@ -34,14 +36,16 @@ pub fn stub_span_info<'a>(
 ) -> Span<'a> {
     let span_info = Span {
         source: Source {
-            source: Cow::from(input),
+            source: Text::from(input),
             filename: None,
+            first_line: 1,
         },
         name: None,
         input: Cow::from(input),
         start_pos: SpanPosition::from_tuple(start_pos),
         end_pos: SpanPosition::from_tuple(end_pos),
         inner: None,
+        comments: Vec::new(),
     };
     span_info
 }
@@ -0,0 +1,53 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_data_structures::{list, Value};
+use minilisp_parser::{parse_program, parse_source, Result};
+
+#[test]
+fn test_plain_characters() -> Result<'static, ()> {
+    assert_equal!(parse_source("?a")?, Value::char('a'));
+    assert_equal!(parse_source("?é")?, Value::char('é'));
+    assert_equal!(
+        parse_source("(list ?a ?b)")?,
+        list([Value::symbol("list"), Value::char('a'), Value::char('b')])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_escaped_characters() -> Result<'static, ()> {
+    assert_equal!(parse_source(r"?\n")?, Value::char('\n'));
+    assert_equal!(parse_source(r"?\s")?, Value::char(' '));
+    assert_equal!(parse_source(r"?\d")?, Value::char('\x7f'));
+    assert_equal!(parse_source(r"?\(")?, Value::char('('));
+    assert_equal!(parse_source(r"?\\")?, Value::char('\\'));
+    assert_equal!(parse_source(r"?\x41")?, Value::char('A'));
+    assert_equal!(parse_source(r"?é")?, Value::char('é'));
+    assert_equal!(parse_source(r"?\N{U+1F600}")?, Value::char('\u{1F600}'));
+    assert_equal!(parse_source(r"?\101")?, Value::char('A'));
+    Ok(())
+}
+
+#[test]
+fn test_control_characters() -> Result<'static, ()> {
+    assert_equal!(parse_source(r"?\C-a")?, Value::char('\x01'));
+    assert_equal!(parse_source(r"?\^A")?, Value::char('\x01'));
+    assert_equal!(parse_source(r"?\C-@")?, Value::char('\0'));
+    assert_equal!(parse_source(r"?\C-?")?, Value::char('\x7f'));
+    Ok(())
+}
+
+#[test]
+fn test_displayed_characters_read_back() -> Result<'static, ()> {
+    for c in ['a', '\n', ' ', '\x01', '\0', '(', '?', 'é'] {
+        let printed: &'static str = Value::char(c).to_string().leak();
+        assert_equal!(parse_source(printed)?, Value::char(c));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_invalid_characters_are_errors() {
+    assert_equal!(parse_source(r"?\C-1").is_err(), true);
+    assert_equal!(parse_source(r"?\x110000").is_err(), true);
+}
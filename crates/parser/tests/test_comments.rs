@@ -0,0 +1,112 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_data_structures::{list, Value};
+use minilisp_parser::{parse_program, parse_program_with_comments, parse_source, Result};
+
+#[test]
+fn test_line_comments() -> Result<'static, ()> {
+    let forms = parse_program(
+        r#";; explanation
+(setq a 1) ; trailing
+(list a ; inside
+  2)
+"#,
+    )?;
+    assert_equal!(
+        forms.iter().map(|form| form.value()).collect::<Vec<Value>>(),
+        vec![
+            list([
+                Value::symbol("setq"),
+                Value::symbol("a"),
+                Value::unsigned_integer(1u32),
+            ]),
+            list([
+                Value::symbol("list"),
+                Value::symbol("a"),
+                Value::unsigned_integer(2u32),
+            ]),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_nested_block_comments() -> Result<'static, ()> {
+    let value = parse_source(
+        r#"#| outer #| inner |# still outer |#
+(list 1 #| between |# 2)"#,
+    )?;
+    assert_equal!(
+        value,
+        list([
+            Value::symbol("list"),
+            Value::unsigned_integer(1u32),
+            Value::unsigned_integer(2u32),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_datum_comments() -> Result<'static, ()> {
+    let forms = parse_program(r#"#;(ignored form) (list 1 #;2 #;"x" 3)"#)?;
+    assert_equal!(forms.len(), 1);
+    assert_equal!(
+        forms[0].value(),
+        list([
+            Value::symbol("list"),
+            Value::unsigned_integer(1u32),
+            Value::unsigned_integer(3u32),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_comments_are_kept_as_trivia() -> Result<'static, ()> {
+    let forms = parse_program(
+        r#";; first
+#| second |#
+(list 1 ; third
+  2)"#,
+    )?;
+    let comments = forms[0].span.comments();
+    assert_equal!(
+        comments.iter().map(|span| span.input().to_string()).collect::<Vec<String>>(),
+        vec![";; first".to_string(), "#| second |#".to_string()]
+    );
+    let inner = forms[0].span.inner();
+    let two = inner.iter().find(|span| span.input() == "2").unwrap();
+    assert_equal!(
+        two.comments().iter().map(|span| span.input().to_string()).collect::<Vec<String>>(),
+        vec!["; third".to_string()]
+    );
+    assert_equal!(two.comments()[0].is_comment(), true);
+    Ok(())
+}
+
+#[test]
+fn test_comments_after_the_last_form_are_kept() -> Result<'static, ()> {
+    let (forms, comments) =
+        parse_program_with_comments("(a) ;; trailing\n#| block |#", None)?;
+    assert_equal!(forms.len(), 1);
+    assert_equal!(
+        comments.iter().map(|span| span.input().to_string()).collect::<Vec<String>>(),
+        vec![";; trailing".to_string(), "#| block |#".to_string()]
+    );
+    assert_equal!(comments[1].start_pos(), (2, 1));
+    Ok(())
+}
+
+#[test]
+fn test_comments_of_a_file_without_forms_are_kept() -> Result<'static, ()> {
+    let (forms, comments) =
+        parse_program_with_comments(";;; only.el\n#;(skipped)\n; done\n", Some("only.el"))?;
+    assert_equal!(forms.len(), 0);
+    assert_equal!(
+        comments.iter().map(|span| span.input().to_string()).collect::<Vec<String>>(),
+        vec![";;; only.el".to_string(), "#;(skipped)".to_string(), "; done".to_string()]
+    );
+    assert_equal!(comments[2].location(), "only.el:3:1");
+    Ok(())
+}
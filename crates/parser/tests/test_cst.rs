@@ -0,0 +1,133 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_parser::{parse_cst, parse_program, Result, SyntaxElement, SyntaxKind};
+
+const PROGRAM: &str = r#";;; header comment
+(defun f (x &optional y)   ; trailing comment
+  "docstring with \"escapes\"\n"
+  #| block #| nested |# comment |#
+  (list 'a '(b . c) `(d ,x ,@y) [1 2.5 #x1F ?\C-a] t nil))
+
+#;(ignored datum)
+	'symbol   -1e+INF  ; tab above
+"#;
+
+fn kinds(input: &str) -> Vec<SyntaxKind> {
+    parse_cst(input, None)
+        .expect("cst")
+        .root
+        .tokens()
+        .iter()
+        .map(|token| token.kind)
+        .collect()
+}
+
+#[test]
+fn test_round_trips_byte_for_byte() -> Result<'static, ()> {
+    for input in [PROGRAM, "", "  \n", "a", "(a)\r\n(b)\n", "( a  .  b )", "[ ]"] {
+        assert_equal!(parse_cst(input, None)?.to_string(), input);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_lowers_to_the_values_the_parser_reads() -> Result<'static, ()> {
+    let values = parse_cst(PROGRAM, None)?.lower()?;
+    let expected = parse_program(PROGRAM)?
+        .into_iter()
+        .map(|form| form.value)
+        .collect::<Vec<_>>();
+    assert_equal!(values, expected);
+    Ok(())
+}
+
+#[test]
+fn test_lowers_escaped_symbols_like_the_parser() -> Result<'static, ()> {
+    let input = r"'a\ b (c\(d . 'e\;f)";
+    let values = parse_cst(input, None)?.lower()?;
+    let expected = parse_program(input)?
+        .into_iter()
+        .map(|form| form.value)
+        .collect::<Vec<_>>();
+    assert_equal!(values, expected);
+    Ok(())
+}
+
+#[test]
+fn test_lowers_quoted_datums_like_the_parser() -> Result<'static, ()> {
+    let input = r#"'42 '"s" '[1 'b] ''a '(c ''d) ' e"#;
+    let values = parse_cst(input, None)?.lower()?;
+    let expected = parse_program(input)?
+        .into_iter()
+        .map(|form| form.value)
+        .collect::<Vec<_>>();
+    assert_equal!(values, expected);
+    Ok(())
+}
+
+#[test]
+fn test_keeps_trivia_and_sugar_as_tokens() {
+    assert_equal!(
+        kinds("'(a . ;c\n b)"),
+        vec![
+            SyntaxKind::Quote,
+            SyntaxKind::OpenParen,
+            SyntaxKind::Symbol,
+            SyntaxKind::Whitespace,
+            SyntaxKind::Dot,
+            SyntaxKind::Whitespace,
+            SyntaxKind::LineComment,
+            SyntaxKind::Whitespace,
+            SyntaxKind::Symbol,
+            SyntaxKind::CloseParen,
+        ]
+    );
+    assert_equal!(
+        kinds("`(,a ,@b)"),
+        vec![
+            SyntaxKind::Backquote,
+            SyntaxKind::OpenParen,
+            SyntaxKind::Comma,
+            SyntaxKind::Symbol,
+            SyntaxKind::Whitespace,
+            SyntaxKind::CommaAt,
+            SyntaxKind::Symbol,
+            SyntaxKind::CloseParen,
+        ]
+    );
+}
+
+#[test]
+fn test_classifies_comments() {
+    let kinds = kinds(PROGRAM);
+    for kind in [
+        SyntaxKind::LineComment,
+        SyntaxKind::BlockComment,
+        SyntaxKind::DatumComment,
+    ] {
+        assert!(kinds.contains(&kind), "{:?} in {:?}", kind, kinds);
+    }
+}
+
+#[test]
+fn test_positions() -> Result<'static, ()> {
+    let tree = parse_cst("; c\n(a\n  \"é\" b)", Some("file.el"))?;
+    let form = tree.forms().next().expect("form");
+    assert_equal!(form.kind(), SyntaxKind::List);
+    let span = tree.span(form);
+    assert_equal!(span.start_pos(), (2, 1));
+    assert_equal!(span.end_pos(), (3, 9));
+    assert_equal!(span.location(), "file.el:2:1");
+    let SyntaxElement::Node(list) = form else {
+        panic!("expected a node")
+    };
+    let symbol = list.datums().nth(2).expect("b");
+    assert_equal!(symbol.to_string(), "b");
+    assert_equal!(symbol.start_pos().to_tuple(), (3, 7));
+    Ok(())
+}
+
+#[test]
+fn test_syntax_error() {
+    assert_equal!(parse_cst("(a", None).is_err(), true);
+}
@@ -0,0 +1,103 @@
+#![allow(unused)]
+use std::io::Cursor;
+
+use k9::assert_equal;
+use minilisp_data_structures::{list, Value};
+use minilisp_parser::errors::Traceback;
+use minilisp_parser::{
+    dispatch_macro, parse_cst, parse_program, parse_source, register_dispatch_macro,
+    unregister_dispatch_macro, Error, Reader, Result,
+};
+
+fn date<'a>(datum: Value<'a>) -> Result<'a, Value<'a>> {
+    match &datum {
+        Value::String(date) if date.len() == 10 =>
+            Ok(list([Value::symbol("date"), datum.clone()])),
+        datum => Err(Error::new(format!("invalid date: {}", datum), None)),
+    }
+}
+
+#[test]
+fn test_function_quote() -> Result<'static, ()> {
+    assert_equal!(parse_source("#'car")?.to_string(), "(function car)");
+    assert_equal!(
+        parse_source("(mapcar #'(lambda (x) x) l)")?.to_string(),
+        "(mapcar (function (lambda (x) x)) l)"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_record() -> Result<'static, ()> {
+    assert_equal!(parse_source("#s(point 1 2)")?.to_string(), "[point 1 2]");
+    Ok(())
+}
+
+#[test]
+fn test_radix_literals_are_not_dispatched() -> Result<'static, ()> {
+    assert_equal!(parse_source("#x1F")?, Value::unsigned_integer(31u32));
+    assert_equal!(parse_source("#foo")?.to_string(), "#foo");
+    Ok(())
+}
+
+#[test]
+fn test_custom_dispatch_macro() -> Result<'static, ()> {
+    register_dispatch_macro("date", date);
+    assert!(dispatch_macro("date").is_some());
+    assert_equal!(
+        parse_source(r#"(deadline #date"2026-01-01")"#)?.to_string(),
+        r#"(deadline (date "2026-01-01"))"#
+    );
+    let error = parse_source(r#"(deadline #date"soon")"#).unwrap_err();
+    assert_equal!(error.message(), "invalid date: \"soon\"");
+    assert_equal!(error.span().expect("span").start_pos(), (1, 11));
+    Ok(())
+}
+
+#[test]
+fn test_unknown_dispatch_macro() {
+    register_dispatch_macro("gone", date);
+    unregister_dispatch_macro("gone");
+    let error = parse_source("#gone(1)").unwrap_err();
+    assert_equal!(error.message(), "unknown reader macro #gone");
+    assert_equal!(error.span().expect("span").start_pos(), (1, 1));
+}
+
+#[test]
+fn test_dispatch_in_streams_and_syntax_trees() -> Result<'static, ()> {
+    let input = "#s(a b) #'f\n#s[c]";
+    let mut reader = Reader::new(Cursor::new(input));
+    assert_equal!(reader.read_form()?.expect("form").value.to_string(), "[a b]");
+    assert_equal!(reader.read_form()?.expect("form").value.to_string(), "(function f)");
+    let error = reader.read_form().unwrap_err();
+    assert_equal!(error.message(), "invalid record syntax: #s[c]");
+    assert_equal!(error.span().expect("span").start_pos(), (2, 1));
+    let tree = parse_cst("#s(a b) #'f", None)?;
+    assert_equal!(tree.to_string(), "#s(a b) #'f");
+    assert_equal!(
+        tree.lower()?,
+        parse_program("#s(a b) #'f")?
+            .into_iter()
+            .map(|form| form.value)
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_hash_table_syntax() -> Result<'static, ()> {
+    let value = parse_source("#s(hash-table size 3 test equal data (a 1 \"b\" (2)))")?;
+    match &value {
+        Value::HashTable(table) => {
+            assert_equal!(table.len(), 2);
+            let value = table.get(&Value::string("b")).unwrap_or_default();
+            assert_equal!(value.to_string(), "(2)");
+        },
+        value => panic!("expected a hash table, got {}", value),
+    }
+    assert_equal!(value.to_string(), r#"#s(hash-table test equal data (a 1 "b" (2)))"#);
+    assert_equal!(parse_source("#s(hash-table)")?.to_string(), "#s(hash-table)");
+    let error = parse_source("#s(hash-table test same)").unwrap_err();
+    assert_equal!(error.message(), "invalid hash table syntax: #s(hash-table test same)");
+    Ok(())
+}
@@ -0,0 +1,113 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_data_structures::{list, BigInteger, Rational, Value};
+use minilisp_parser::{parse_source, Result};
+
+#[test]
+fn test_decimal_integers() -> Result<'static, ()> {
+    assert_equal!(parse_source("42")?, Value::unsigned_integer(42u32));
+    assert_equal!(parse_source("+5")?, Value::unsigned_integer(5u32));
+    assert_equal!(parse_source("-5")?, Value::integer(-5i64));
+    assert_equal!(parse_source("1.")?, Value::unsigned_integer(1u32));
+    assert_equal!(parse_source("99999999999")?, Value::integer(99999999999i64));
+    Ok(())
+}
+
+#[test]
+fn test_radix_integers() -> Result<'static, ()> {
+    assert_equal!(parse_source("#x1F")?, Value::unsigned_integer(31u32));
+    assert_equal!(parse_source("#b1010")?, Value::unsigned_integer(10u32));
+    assert_equal!(parse_source("#o17")?, Value::unsigned_integer(15u32));
+    assert_equal!(parse_source("#x-ff")?, Value::integer(-255i64));
+    assert_equal!(parse_source("#24r1k")?, Value::unsigned_integer(44u32));
+    Ok(())
+}
+
+#[test]
+fn test_floats() -> Result<'static, ()> {
+    assert_equal!(parse_source("1e10")?, Value::float(1e10));
+    assert_equal!(parse_source(".5")?, Value::float(0.5));
+    assert_equal!(parse_source("1.0e-3")?, Value::float(0.001));
+    assert_equal!(parse_source("-1.5")?, Value::float(-1.5));
+    assert_equal!(parse_source("1.0e+INF")?, Value::float(f64::INFINITY));
+    assert_equal!(parse_source("-1.0e+INF")?, Value::float(f64::NEG_INFINITY));
+    match parse_source("0.0e+NaN")? {
+        Value::Float(float) => assert!(float.is_nan()),
+        value => panic!("expected NaN, got {:#?}", value),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_numbers_within_lists() -> Result<'static, ()> {
+    assert_equal!(
+        parse_source("(+ 1 -2 .5 #xA)")?,
+        list([
+            Value::symbol("+"),
+            Value::unsigned_integer(1u32),
+            Value::integer(-2i64),
+            Value::float(0.5),
+            Value::unsigned_integer(10u32),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_symbols_that_look_like_numbers() -> Result<'static, ()> {
+    assert_equal!(parse_source("1+")?, Value::symbol("1+"));
+    assert_equal!(parse_source("foo1")?, Value::symbol("foo1"));
+    assert_equal!(parse_source("-")?, Value::symbol("-"));
+    assert_equal!(parse_source("+")?, Value::symbol("+"));
+    Ok(())
+}
+
+#[test]
+fn test_integers_beyond_i64_are_big_integers() -> Result<'static, ()> {
+    assert_equal!(
+        parse_source("99999999999999999999")?,
+        Value::BigInteger("99999999999999999999".parse().expect("digits"))
+    );
+    assert_equal!(
+        parse_source("-9223372036854775809")?,
+        Value::BigInteger("-9223372036854775809".parse().expect("digits"))
+    );
+    assert_equal!(
+        parse_source("#x10000000000000000")?,
+        Value::BigInteger("18446744073709551616".parse().expect("digits"))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rationals() -> Result<'static, ()> {
+    assert_equal!(
+        parse_source("2/6")?,
+        Value::Rational(Rational::new(BigInteger::from(1u32), BigInteger::from(3u32)))
+    );
+    assert_equal!(
+        parse_source("-1/2")?,
+        Value::Rational(Rational::new(BigInteger::from(-1i64), BigInteger::from(2u32)))
+    );
+    assert_equal!(parse_source("4/2")?, Value::unsigned_integer(2u32));
+    assert_equal!(parse_source("1/")?, Value::symbol("1/"));
+    assert_equal!(parse_source("1/2a")?, Value::symbol("1/2a"));
+    Ok(())
+}
+
+#[test]
+fn test_zero_denominator_is_an_error() {
+    let error = parse_source("(list 1/0)").unwrap_err();
+    assert_equal!(
+        error.to_string().contains("zero denominator in rational literal \"1/0\""),
+        true
+    );
+    let span = error.span().expect("span");
+    assert_equal!(span.start_pos(), (1, 7));
+    assert_equal!(span.end_pos(), (1, 10));
+}
+
+#[test]
+fn test_invalid_radix_is_an_error() {
+    assert_equal!(parse_source("#40r1").is_err(), true);
+}
@@ -1,8 +1,8 @@
 #![allow(unused)]
 use k9::assert_equal;
-use minilisp_data_structures::{list, Value};
+use minilisp_data_structures::{car, cdr, list, pair, Value};
 use minilisp_parser::test::stub_input;
-use minilisp_parser::{parse_source, Result};
+use minilisp_parser::{parse_program, parse_program_with_filename, parse_source, Result};
 use minilisp_util::vec_deque;
 
 #[test]
@@ -126,3 +126,202 @@ fn test_defun() -> Result<'static, ()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_parse_program_returns_every_top_level_form() -> Result<'static, ()> {
+    let forms = parse_program(
+        r#"(setq a 1)
+(setq b 2)
+(+ a b)"#,
+    )?;
+    assert_equal!(forms.len(), 3);
+    assert_equal!(
+        forms.iter().map(|form| form.value.clone()).collect::<Vec<Value>>(),
+        vec![
+            list([
+                Value::symbol("setq"),
+                Value::symbol("a"),
+                Value::unsigned_integer(1u32),
+            ]),
+            list([
+                Value::symbol("setq"),
+                Value::symbol("b"),
+                Value::unsigned_integer(2u32),
+            ]),
+            list([
+                Value::symbol("+"),
+                Value::symbol("a"),
+                Value::symbol("b"),
+            ]),
+        ]
+    );
+    assert_equal!(forms[0].span.start_pos(), (1, 1));
+    assert_equal!(forms[0].span.end_pos(), (1, 11));
+    assert_equal!(forms[1].span.start_pos(), (2, 1));
+    assert_equal!(forms[2].span.input(), "(+ a b)");
+    Ok(())
+}
+
+#[test]
+fn test_parse_program_of_empty_input() -> Result<'static, ()> {
+    assert_equal!(parse_program("")?.len(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_dotted_pair() -> Result<'static, ()> {
+    let items = parse_source(r#"'(a . b)"#)?;
+    assert_equal!(items, pair(Value::symbol("a"), Value::symbol("b")).quote());
+    assert_equal!(items.to_string(), "'(a . b)");
+    Ok(())
+}
+
+#[test]
+fn test_improper_list() -> Result<'static, ()> {
+    let items = parse_source(r#"(a b . c)"#)?;
+    assert_equal!(
+        items,
+        pair(
+            Value::symbol("a"),
+            pair(Value::symbol("b"), Value::symbol("c"))
+        )
+    );
+    assert_equal!(items.to_string(), "(a b . c)");
+    Ok(())
+}
+
+#[test]
+fn test_dotted_list_tail_is_spliced() -> Result<'static, ()> {
+    let items = parse_source(r#"(a . (b c))"#)?;
+    assert_equal!(
+        items,
+        list([Value::symbol("a"), Value::symbol("b"), Value::symbol("c")])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_dot_within_symbol_is_not_a_pair() -> Result<'static, ()> {
+    let items = parse_source(r#"(a.b c)"#)?;
+    assert_equal!(items, list([Value::symbol("a.b"), Value::symbol("c")]));
+    Ok(())
+}
+
+#[test]
+fn test_quasiquote() -> Result<'static, ()> {
+    let items = parse_source(r#"`(a ,b ,@rest)"#)?;
+    assert_equal!(
+        items,
+        list([
+            Value::symbol("backquote"),
+            list([
+                Value::symbol("a"),
+                list([Value::symbol(","), Value::symbol("b")]),
+                list([Value::symbol(",@"), Value::symbol("rest")]),
+            ]),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_nested_quasiquote() -> Result<'static, ()> {
+    let items = parse_source(r#"`(a `(b ,(c ,d)))"#)?;
+    assert_equal!(items.to_string(), "(backquote (a (backquote (b (, (c (, d)))))))");
+    Ok(())
+}
+
+#[test]
+fn test_parse_program_of_bare_atoms() -> Result<'static, ()> {
+    let forms = parse_program(r#"42 "hello" foo 'foo t nil test"#)?;
+    assert_equal!(
+        forms.iter().map(|form| form.value.clone()).collect::<Vec<Value>>(),
+        vec![
+            Value::unsigned_integer(42u32),
+            Value::from("hello"),
+            Value::symbol("foo"),
+            Value::quoted_symbol("foo"),
+            Value::T,
+            Value::nil(),
+            Value::symbol("test"),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_quote_any_datum() -> Result<'static, ()> {
+    let quote = |value| list([Value::symbol("quote"), value]);
+    assert_equal!(parse_source("'42")?, quote(Value::unsigned_integer(42u32)));
+    assert_equal!(parse_source(r#"'"s""#)?, quote(Value::from("s")));
+    assert_equal!(
+        parse_source("'[1 a]")?,
+        quote(Value::vector([Value::unsigned_integer(1u32), Value::symbol("a")]))
+    );
+    assert_equal!(parse_source("''a")?, quote(Value::quoted_symbol("a")));
+    assert_equal!(parse_source("'''a")?.to_string(), "'(quote 'a)");
+    assert_equal!(parse_source("'nil")?, quote(Value::Nil));
+    assert_equal!(parse_source("'a")?, Value::quoted_symbol("a"));
+    assert_equal!(parse_source("'(a)")?, Value::quoted_list([Value::symbol("a")]));
+    assert_equal!(parse_source("(list '1 'b)")?.to_string(), "(list (quote 1) 'b)");
+    Ok(())
+}
+
+#[test]
+fn test_parse_source_of_bare_symbol() -> Result<'static, ()> {
+    assert_equal!(parse_source("nilly")?, Value::symbol("nilly"));
+    Ok(())
+}
+
+#[test]
+fn test_vector() -> Result<'static, ()> {
+    assert_equal!(
+        parse_source("[1 foo \"bar\" (a b) [2]]")?,
+        Value::vector([
+            Value::unsigned_integer(1u32),
+            Value::symbol("foo"),
+            Value::from("bar"),
+            list([Value::symbol("a"), Value::symbol("b")]),
+            Value::vector([Value::unsigned_integer(2u32)]),
+        ])
+    );
+    assert_equal!(parse_source("[]")?, Value::vector([]));
+    assert_equal!(parse_source("[a[b]c]")?.to_string(), "[a [b] c]");
+    Ok(())
+}
+
+#[test]
+fn test_forms_keep_spans_of_lists_and_atoms() -> Result<'static, ()> {
+    let forms = parse_program("(a\n  (b c) \"d\")")?;
+    let spans = forms[0].spans();
+    let form = &forms[0].value;
+    let inner = car(&cdr(form));
+    assert_equal!(spans.get(&inner).expect("span of (b c)").start_pos(), (2, 3));
+    assert_equal!(spans.get(&inner).expect("span of (b c)").end_pos(), (2, 8));
+    assert_equal!(spans.get_element(&inner, 1).expect("span of c").start_pos(), (2, 6));
+    assert_equal!(spans.get_in(&Value::from("d"), form).expect("span of d").start_pos(), (2, 9));
+    assert_equal!(spans.get(form).expect("span of form").start_pos(), (1, 1));
+
+    // an equal list read elsewhere is another list
+    assert_equal!(spans.get(&list([Value::symbol("b"), Value::symbol("c")])), None);
+    Ok(())
+}
+
+#[test]
+fn test_spans_render_location_and_excerpt() -> Result<'static, ()> {
+    let forms = parse_program_with_filename("(a)\n(b\n c)", Some("file.el"))?;
+    let span = forms[1].span();
+    assert_equal!(span.location(), "file.el:2:1");
+    let excerpt = span.highlight_input(0);
+    assert_equal!(excerpt.contains("--> file.el:2:1"), true);
+    assert_equal!(excerpt.contains("2 | "), true);
+    assert_equal!(excerpt.contains("3 | "), true);
+    assert_equal!(excerpt.contains("1 | "), false);
+    Ok(())
+}
+
+#[test]
+fn test_parse_error_reports_filename() {
+    let error = parse_program_with_filename("(a\n #x", Some("file.el")).unwrap_err();
+    assert_equal!(error.span().expect("span").location().starts_with("file.el:"), true);
+}
@@ -0,0 +1,218 @@
+use k9::assert_equal;
+use minilisp_data_structures::{list, prin1, princ, BigInteger, Cell, Rational, Value};
+use minilisp_parser::{parse_source, Result};
+
+/// Reads back the output of [`prin1`].
+fn read(printed: String) -> Value<'static> {
+    let printed: &'static str = printed.leak();
+    parse_source(printed).unwrap_or_else(|error| panic!("{:?}", error))
+}
+
+/// xorshift64*, enough to generate values deterministically.
+struct Random(u64);
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+
+    fn char(&mut self) -> char {
+        match self.below(4) {
+            0 => self.pick(&[
+                '\n', '\t', '\r', '\0', '\x07', '\x1b', '\x7f', ' ', '"', '\\', '(', ')', '[',
+                ']', ';', '\'', '`', ',', '.', '#', '?', '|',
+            ]),
+            1 => char::from_u32(self.below(0x20) as u32).expect("control"),
+            2 => self.pick(&['é', 'λ', '字', '😀', '\u{a0}', '\u{200b}']),
+            _ => (b'!' + self.below(94) as u8) as char,
+        }
+    }
+
+    fn text(&mut self, min: u64) -> String {
+        (0..min + self.below(8)).map(|_| self.char()).collect()
+    }
+
+    fn symbol(&mut self) -> String {
+        match self.below(4) {
+            0 => self
+                .pick(&[
+                    "nil", "t", ".", "1", "-2.5", "+1", "1.", "1e5", ".5", "1.0e+INF", "?a",
+                    "#x1F", "#s", "a b", "\\", "1/3", "-2/4",
+                ])
+                .to_string(),
+            1 => self.text(1),
+            _ => (0..1 + self.below(6))
+                .map(|_| {
+                    self.pick(&['a', 'z', '-', '+', '*', '/', '<', '=', '>', '!', '&', ':'])
+                })
+                .collect(),
+        }
+    }
+
+    /// An integer of one to four 64-bit words, beyond `i64`.
+    fn big_integer(&mut self) -> BigInteger {
+        let digits = (0..1 + self.below(4)).map(|_| self.next().to_string()).collect::<String>();
+        let sign = self.pick(&["", "-"]);
+        format!("{}{}{}", sign, u64::MAX, digits).parse().expect("digits")
+    }
+
+    fn float(&mut self) -> f64 {
+        match self.below(3) {
+            0 => self.pick(&[
+                0.0,
+                -0.0,
+                1.0,
+                -1.5,
+                0.1,
+                1e300,
+                -1e-300,
+                f64::MAX,
+                f64::MIN_POSITIVE,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NAN,
+                -f64::NAN,
+            ]),
+            1 => (self.next() as i64) as f64 / 1e6,
+            _ => f64::from_bits(self.next()),
+        }
+    }
+
+    /// A value of any variant the reader produces, nested up to
+    /// `depth` lists and vectors deep.
+    fn value(&mut self, depth: usize) -> Value<'static> {
+        let variants = if depth == 0 { 11 } else { 15 };
+        match self.below(variants) {
+            0 => Value::Nil,
+            1 => Value::T,
+            2 => Value::string(self.text(0)),
+            3 => Value::char(self.char()),
+            4 => Value::symbol(self.symbol()),
+            5 => Value::quoted_symbol(self.symbol()),
+            6 => Value::unsigned_integer(self.next() as u32),
+            7 => Value::integer(match self.below(2) {
+                0 => -1 - self.below(i64::MAX as u64) as i64,
+                _ =>
+                    u32::MAX as i64
+                        + 1
+                        + self.below((i64::MAX - u32::MAX as i64) as u64) as i64,
+            }),
+            8 => {
+                let float = self.float();
+                if float.is_nan() {
+                    // the reader produces the canonical NaN
+                    Value::from(f64::NAN.copysign(float))
+                } else {
+                    Value::from(float)
+                }
+            },
+            9 => Value::BigInteger(self.big_integer()),
+            10 => {
+                // a remainder below the denominator keeps it from
+                // reducing to an integer
+                let denominator = 2 + self.below(u32::MAX as u64 - 1);
+                let remainder = 1 + self.below(denominator - 1);
+                Value::Rational(Rational::new(
+                    BigInteger::from(self.next() as i64) * BigInteger::from(denominator as u32)
+                        + BigInteger::from(remainder as u32),
+                    BigInteger::from(denominator as u32),
+                ))
+            },
+            11 | 12 => {
+                let items =
+                    (0..self.below(5)).map(|_| self.value(depth - 1)).collect::<Vec<_>>();
+                let mut value = Value::from_iter(items);
+                if !value.is_empty() && self.below(3) == 0 {
+                    let mut cell = match value {
+                        Value::List(ref cell) => cell.clone(),
+                        _ => unreachable!(),
+                    };
+                    cell.set_dotted_tail(self.atom());
+                    value = Value::list(cell);
+                }
+                match &value {
+                    Value::List(cell) if self.below(3) == 0 => Value::QuotedList(cell.clone()),
+                    _ => value,
+                }
+            },
+            _ => Value::vector((0..self.below(5)).map(|_| self.value(depth - 1))),
+        }
+    }
+
+    /// A value that stays the tail of a dotted list when read.
+    fn atom(&mut self) -> Value<'static> {
+        loop {
+            let value = self.value(0);
+            if !value.is_nil() {
+                return value;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_prin1_round_trips_every_variant() {
+    let mut random = Random(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..5000 {
+        let value = random.value(3);
+        let printed = prin1(&value);
+        assert_eq!(read(printed.clone()), value, "read back {}", printed);
+    }
+}
+
+#[test]
+fn test_prin1_round_trips_to_the_canonical_representation() {
+    assert_equal!(read(prin1(&Value::Byte(255))), Value::unsigned_integer(255u32));
+    assert_equal!(read(prin1(&Value::integer(7))), Value::unsigned_integer(7u32));
+    assert_equal!(read(prin1(&Value::EmptyList)), Value::List(Cell::nil()));
+    assert_equal!(read(prin1(&Value::EmptyQuotedList)), Value::QuotedList(Cell::nil()));
+}
+
+#[test]
+fn test_prin1() {
+    assert_equal!(prin1(&Value::Byte(10)), "10");
+    assert_equal!(prin1(&Value::from(1.0)), "1.0");
+    assert_equal!(prin1(&Value::from(f64::NEG_INFINITY)), "-1.0e+INF");
+    assert_equal!(prin1(&Value::from(f64::NAN)), "0.0e+NaN");
+    assert_equal!(prin1(&Value::string("a \"b\"\n")), r#""a \"b\"\n""#);
+    assert_equal!(prin1(&Value::char(' ')), r"?\s");
+    assert_equal!(prin1(&Value::symbol("a b(c)")), r"a\ b\(c\)");
+    assert_equal!(prin1(&Value::symbol("12")), r"\12");
+    assert_equal!(prin1(&Value::symbol("nil")), r"\nil");
+    assert_equal!(prin1(&Value::symbol("?x")), r"\?x");
+    assert_equal!(prin1(&Value::quoted_symbol("a;")), r"'a\;");
+    assert_equal!(
+        prin1(&list([Value::symbol("a"), Value::string("b"), Value::vector([Value::T])])),
+        r#"(a "b" [t])"#
+    );
+}
+
+#[test]
+fn test_princ() {
+    assert_equal!(princ(&Value::string("a \"b\"\n")), "a \"b\"\n");
+    assert_equal!(princ(&Value::char('x')), "x");
+    assert_equal!(princ(&Value::symbol("a b")), "a b");
+    assert_equal!(
+        princ(&list([Value::symbol("a"), Value::string("b c"), Value::from(2.0)])),
+        "(a b c 2.0)"
+    );
+}
+
+#[test]
+fn test_reads_escaped_symbols() -> Result<'static, ()> {
+    assert_equal!(parse_source(r"a\ b")?, Value::symbol("a b"));
+    assert_equal!(parse_source(r"\1")?, Value::symbol("1"));
+    assert_equal!(parse_source(r"'\(")?, Value::quoted_symbol("("));
+    assert_equal!(parse_source(r"(\nil)")?.to_string(), "(nil)");
+    Ok(())
+}
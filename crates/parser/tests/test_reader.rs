@@ -0,0 +1,111 @@
+#![allow(unused)]
+use std::io::{Cursor, Read};
+
+use k9::assert_equal;
+use minilisp_parser::errors::Traceback;
+use minilisp_data_structures::{car, cdr};
+use minilisp_parser::{Reader, Result};
+
+fn values(input: &str) -> Vec<String> {
+    Reader::new(Cursor::new(input.to_string()))
+        .map(|form| form.expect("form").value.to_string())
+        .collect()
+}
+
+/// Fails the test if the reader pulls more input than it needs.
+struct Unreadable;
+impl Read for Unreadable {
+    fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+        panic!("read past the end of the form")
+    }
+}
+
+#[test]
+fn test_reads_forms_in_order() {
+    assert_equal!(
+        values("(a 1) b\n; comment\n\"c d\" [e (f)]\n'g"),
+        vec!["(a 1)", "b", "\"c d\"", "[e (f)]", "'g"]
+    );
+}
+
+#[test]
+fn test_reads_forms_spanning_lines() {
+    assert_equal!(
+        values("(defun f (x)\n  \"a\nb\"\n(g x))\n(h)"),
+        vec!["(defun f (x) \"a\\nb\" (g x))", "(h)"]
+    );
+}
+
+#[test]
+fn test_empty_input() {
+    assert_equal!(values(""), Vec::<String>::new());
+    assert_equal!(values("  ; only a comment\n"), Vec::<String>::new());
+}
+
+#[test]
+fn test_reads_only_as_much_input_as_needed() -> Result<'static, ()> {
+    let mut reader = Reader::from_read(Cursor::new("(a\n  1) (b)\n").chain(Unreadable));
+    assert_equal!(reader.read_form()?.expect("form").value.to_string(), "(a 1)");
+    assert_equal!(reader.read_form()?.expect("form").value.to_string(), "(b)");
+    Ok(())
+}
+
+#[test]
+fn test_spans_are_relative_to_the_whole_input() -> Result<'static, ()> {
+    let mut reader =
+        Reader::new(Cursor::new("(a)\n\n  (b\n c) (d)")).with_filename("input.el");
+    reader.read_form()?;
+    let form = reader.read_form()?.expect("form");
+    assert_equal!(form.span.start_pos(), (3, 3));
+    assert_equal!(form.span.end_pos(), (4, 4));
+    assert_equal!(form.span.location(), "input.el:3:3");
+    let form = reader.read_form()?.expect("form");
+    assert_equal!(form.span.start_pos(), (4, 5));
+    assert_equal!(reader.read_form()?, None);
+    Ok(())
+}
+
+#[test]
+fn test_recovers_after_syntax_error() {
+    let mut reader = Reader::new(Cursor::new("(a)\n)\n(b)\n(c"));
+    assert_equal!(reader.next().expect("form").expect("form").value.to_string(), "(a)");
+    let error = reader.next().expect("error").unwrap_err();
+    assert_equal!(error.span().expect("span").start_pos(), (2, 1));
+    assert_equal!(reader.next().expect("form").expect("form").value.to_string(), "(b)");
+    let error = reader.next().expect("error").unwrap_err();
+    assert_equal!(
+        error.message(),
+        "unbalanced parentheses: list is never closed"
+    );
+    assert_equal!(error.span().expect("span").start_pos(), (4, 1));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_forms_outlive_the_reader_with_their_spans() -> Result<'static, ()> {
+    let form = Reader::new(Cursor::new("  (a\n (b \"c\"))".to_string()))
+        .read_form()?
+        .expect("form");
+    let inner = car(&cdr(&form.value));
+    assert_equal!(inner.to_string(), "(b \"c\")");
+    assert_equal!(form.spans.get(&inner).expect("span").start_pos(), (2, 2));
+    assert_equal!(form.span.start_pos(), (1, 3));
+    Ok(())
+}
+
+#[test]
+fn test_reads_long_inputs_with_tokens_spanning_lines() -> Result<'static, ()> {
+    let mut input = String::new();
+    for n in 0..500 {
+        input.push_str(&format!("#| {}\n|# (f {} \"x\ny\") ; (\n", n, n));
+    }
+    let mut reader = Reader::new(Cursor::new(input));
+    for n in 0..500 {
+        let form = reader.read_form()?.expect("form");
+        assert_equal!(form.value.to_string(), format!("(f {} \"x\\ny\")", n));
+        assert_equal!(form.span.start_pos(), (n * 3 + 2, 4));
+    }
+    assert_equal!(reader.read_form()?, None);
+    Ok(())
+}
+
@@ -0,0 +1,75 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_data_structures::{list, Value};
+use minilisp_parser::{parse_program_recovering, Form};
+
+fn values<'a>(forms: &[Form<'a>]) -> Vec<String> {
+    forms.iter().map(|form| form.value.to_string()).collect()
+}
+
+#[test]
+fn test_valid_program_has_no_errors() {
+    let (forms, errors) = parse_program_recovering("(a 1)\n(b \"c\")", None);
+    assert_equal!(values(&forms), vec!["(a 1)", "(b \"c\")"]);
+    assert_equal!(errors.len(), 0);
+}
+
+#[test]
+fn test_recovers_after_stray_close_paren() {
+    let (forms, errors) = parse_program_recovering("(a 1))\n(b 2)", None);
+    assert_equal!(values(&forms), vec!["(a 1)", "(b 2)"]);
+    assert_equal!(errors.len(), 1);
+    assert_equal!(errors[0].span().expect("span").start_pos(), (1, 6));
+}
+
+#[test]
+fn test_recovers_after_unbalanced_open_paren() {
+    let (forms, errors) = parse_program_recovering(
+        "(defun f ()\n  (foo\n(defun g () 1)\n(h",
+        None,
+    );
+    assert_equal!(values(&forms), vec!["(defun g () 1)"]);
+    assert_equal!(errors.len(), 2);
+    assert_equal!(errors[0].span().expect("span").start_pos(), (1, 1));
+    assert_equal!(errors[1].span().expect("span").start_pos(), (4, 1));
+}
+
+#[test]
+fn test_recovers_after_mismatched_bracket() {
+    let (forms, errors) = parse_program_recovering("(a ])\n[b]", None);
+    assert_equal!(values(&forms), vec!["[b]"]);
+    assert_equal!(!errors.is_empty(), true);
+    assert_equal!(errors[0].span().expect("span").start_pos(), (1, 4));
+}
+
+#[test]
+fn test_reports_read_errors_of_each_form() {
+    let (forms, errors) = parse_program_recovering(
+        "(a \"\\q\")\n(b 1/0)\n(c)",
+        Some("bad.el"),
+    );
+    assert_equal!(values(&forms), vec!["(c)"]);
+    assert_equal!(errors.len(), 2);
+    assert_equal!(errors[0].span().expect("span").location(), "bad.el:1:5");
+    assert_equal!(errors[1].span().expect("span").location(), "bad.el:2:4");
+}
+
+#[test]
+fn test_spans_of_recovered_forms_point_into_input() {
+    let (forms, errors) = parse_program_recovering(") (a \"é\") ) (b ?\\()", None);
+    assert_equal!(values(&forms), vec!["(a \"é\")", "(b ?\\()"]);
+    assert_equal!(errors.len(), 2);
+    assert_equal!(forms[1].span().start_pos(), (1, 13));
+    assert_equal!(forms[1].span().input.to_string(), "(b ?\\()");
+}
+
+#[test]
+fn test_keeps_forms_and_comments_around_errors() {
+    let (forms, errors) =
+        parse_program_recovering("; first\n(a)\n(b ])\n#;(c) (d) ]\n(e)", None);
+    assert_equal!(values(&forms), vec!["(a)", "(d)", "(e)"]);
+    assert_equal!(errors.len(), 3);
+    assert_equal!(forms[0].span().comments().len(), 1);
+    assert_equal!(forms[1].span().start_pos(), (4, 7));
+    assert_equal!(forms[2].span().start_pos(), (5, 1));
+}
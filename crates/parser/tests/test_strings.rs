@@ -0,0 +1,64 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_data_structures::{list, Value};
+use minilisp_parser::{parse_source, Result};
+use minilisp_util::Traceback;
+
+#[test]
+fn test_string_escapes() -> Result<'static, ()> {
+    assert_equal!(
+        parse_source(r#"(print "a\tb\nc")"#)?,
+        list([Value::symbol("print"), Value::from("a\tb\nc")])
+    );
+    assert_equal!(
+        parse_source(r#"(print "say \"hi\" \\ bye")"#)?,
+        list([Value::symbol("print"), Value::from("say \"hi\" \\ bye")])
+    );
+    assert_equal!(
+        parse_source(r#"(print "\u{1F600} \u00e9 \x41")"#)?,
+        list([Value::symbol("print"), Value::from("\u{1F600} \u{e9} A")])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_string_keeps_whitespace_and_semicolons() -> Result<'static, ()> {
+    assert_equal!(
+        parse_source(r#"(print "  padded ; not a comment ")"#)?,
+        list([Value::symbol("print"), Value::from("  padded ; not a comment ")])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_multi_line_strings() -> Result<'static, ()> {
+    assert_equal!(
+        parse_source("(print \"first\nsecond \\\n third\")")?,
+        list([Value::symbol("print"), Value::from("first\nsecond  third")])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_display_escapes_strings() -> Result<'static, ()> {
+    let value = parse_source(r#"(print "tab\there \"quoted\" back\\slash\nnewline")"#)?;
+    assert_equal!(
+        value.to_string(),
+        r#"(print "tab\there \"quoted\" back\\slash\nnewline")"#
+    );
+    let printed: &'static str = value.to_string().leak();
+    assert_equal!(parse_source(printed)?, value);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_escape_points_at_escape() {
+    let error = parse_source("(print\n  \"ok \\q\")").unwrap_err();
+    let span = error.span().expect("span");
+    assert_equal!(span.start_pos(), (2, 7));
+    assert_equal!(span.end_pos(), (2, 9));
+    assert_equal!(error.message().contains("\\q"), true);
+
+    let error = parse_source(r#"(print "\u{110000}")"#).unwrap_err();
+    assert_equal!(error.span().expect("span").start_pos(), (1, 9));
+}
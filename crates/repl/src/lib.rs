@@ -4,7 +4,7 @@ use std::borrow::Cow;
 
 pub use errors::{Error, Result};
 use minilisp_formatter::highlight;
-use minilisp_parser::parse_source;
+use minilisp_parser::parse_program;
 use minilisp_util::dbg;
 use minilisp_vm::VirtualMachine;
 use rustyline::completion::{Candidate, Completer};
@@ -178,7 +178,7 @@ impl<'c> Validator for VirtualMachinePrompt<'c> {
         // ValidationResult::Invalid(Option<String>),
         // ValidationResult::Valid(Option<String>),
         // dbg!(ctx);
-        match parse_source(ctx.input()) {
+        match parse_program(ctx.input()) {
             Ok(value) => Ok(ValidationResult::Valid(None)),
             // Ok(value) => Ok(ValidationResult::Valid(Some(format!(" ;; ok.\n")))),
             Err(e) => Ok(ValidationResult::Invalid(Some(e.to_string()))),
@@ -1,6 +1,6 @@
 #![allow(unused)]
 use minilisp_formatter::highlight;
-use minilisp_parser::parse_source;
+use minilisp_parser::parse_program;
 use minilisp_repl::{Result, VirtualMachinePrompt};
 use minilisp_util::color;
 use minilisp_vm::VirtualMachine;
@@ -35,7 +35,7 @@ fn help() {
     println!("\ttype `@' to see the symbol table");
     println!("\ttry arithmetic expressions such as `(* 4 (+ 3 2))'");
 }
-fn repl<'a>() -> Result<()> {
+fn repl() -> Result<()> {
     let config = Config::builder()
         // .history_ignore_dups(true)?
         // .history_ignore_space(false)
@@ -47,7 +47,7 @@ fn repl<'a>() -> Result<()> {
         .check_cursor_position(true)
         .build();
 
-    let mut vm = VirtualMachine::new();
+    let mut vm = VirtualMachine::<'static>::new();
     let vmp = VirtualMachinePrompt::new(&vm);
     let history =
         rustyline::history::FileHistory::with_config(config.clone());
@@ -64,23 +64,26 @@ fn repl<'a>() -> Result<()> {
         let readline = rl.readline(": ");
         match readline {
             Ok(line) => {
-                let line: &'a str = line.clone().leak();
-                rl.add_history_entry(line)?;
+                rl.add_history_entry(&line)?;
                 match line.trim() {
                     "@" => {
                         println!("{:#?}", vm.symbols());
                         continue;
                     },
-                    _ => match parse_source(line) {
-                        Ok(value) => {
-                            println!(
-                                "{}",
-                                highlight(
-                                    vm.eval(value)?.to_string(),
-                                    "lisp"
-                                )?
-                            );
-                        },
+                    _ => match parse_program(&line) {
+                        Ok(forms) =>
+                            for form in forms {
+                                match vm.eval_owned(form) {
+                                    Ok(value) => println!(
+                                        "{}",
+                                        highlight(value.to_string(), "lisp")?
+                                    ),
+                                    Err(error) => {
+                                        print_error(error);
+                                        break;
+                                    },
+                                }
+                            },
                         Err(error) => {
                             print_error(error);
                             continue;
@@ -0,0 +1,31 @@
+use minilisp_data_structures as ds;
+use minilisp_data_structures::Value;
+use minilisp_util::{try_result, with_caller};
+use unique_pointer::UniquePointer;
+
+use crate::{Context, Result};
+
+/// `(eq A B)` is `t` when `A` and `B` are the same object, see
+/// [`Value::is_eq`].
+pub fn eq<'c>(mut vm: UniquePointer<Context<'c>>, list: Value<'c>) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    Ok(boolean(ds::car(&list).is_eq(&ds::car(&ds::cdr(&list)))))
+}
+
+/// `(eql A B)` is `t` when `A` and `B` are `eq` or numbers of the same
+/// type and value, see [`Value::is_eql`].
+pub fn eql<'c>(mut vm: UniquePointer<Context<'c>>, list: Value<'c>) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    Ok(boolean(ds::car(&list).is_eql(&ds::car(&ds::cdr(&list)))))
+}
+
+/// `(equal A B)` is `t` when `A` and `B` have the same structure and
+/// contents, see [`Value::is_equal`].
+pub fn equal<'c>(mut vm: UniquePointer<Context<'c>>, list: Value<'c>) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    Ok(boolean(ds::car(&list).is_equal(&ds::car(&ds::cdr(&list)))))
+}
+
+pub(crate) fn boolean<'c>(value: bool) -> Value<'c> {
+    if value { Value::T } else { Value::Nil }
+}
@@ -0,0 +1,143 @@
+use minilisp_data_structures as ds;
+use minilisp_data_structures::hash_table::Test;
+use minilisp_data_structures::{HashTable, Quotable, Value};
+use minilisp_util::{try_result, with_caller};
+use unique_pointer::UniquePointer;
+
+use crate::helpers::runtime_error;
+use crate::{Context, Function, Result};
+
+/// `(make-hash-table :test TEST)` makes an empty hash table comparing
+/// its keys with `eq`, `eql` (the default) or `equal`.
+pub fn make_hash_table<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let mut test = Test::default();
+    let mut arguments = match &list {
+        Value::List(cell) | Value::QuotedList(cell) => cell.values().into_iter(),
+        _ => Vec::new().into_iter(),
+    };
+    while let Some(keyword) = arguments.next() {
+        let argument = arguments.next().unwrap_or_default();
+        match symbol_name(&keyword) {
+            Some(":test") =>
+                test = match symbol_name(&argument).and_then(Test::from_name) {
+                    Some(test) => test,
+                    None =>
+                        return Err(runtime_error(
+                            format!("invalid hash table test: {}", argument.unquote()),
+                            None,
+                        )),
+                },
+            Some(":size" | ":weakness" | ":rehash-size" | ":rehash-threshold") => {},
+            _ =>
+                return Err(runtime_error(
+                    format!("invalid argument list: {}", keyword),
+                    None,
+                )),
+        }
+    }
+    Ok(Value::HashTable(HashTable::new(test)))
+}
+
+/// `(gethash KEY TABLE &optional DEFAULT)`
+pub fn gethash<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let table = try_result!(hash_table(&ds::car(&ds::cdr(&list))));
+    Ok(table
+        .get(&ds::car(&list))
+        .unwrap_or_else(|| ds::car(&ds::cdr(&ds::cdr(&list))).unquote()))
+}
+
+/// `(puthash KEY VALUE TABLE)` returns `VALUE`.
+pub fn puthash<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let value = ds::car(&ds::cdr(&list)).unquote();
+    let table = try_result!(hash_table(&ds::car(&ds::cdr(&ds::cdr(&list)))));
+    table.insert(ds::car(&list), value.clone());
+    Ok(value)
+}
+
+/// `(remhash KEY TABLE)`
+pub fn remhash<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let table = try_result!(hash_table(&ds::car(&ds::cdr(&list))));
+    table.remove(&ds::car(&list));
+    Ok(Value::Nil)
+}
+
+/// `(maphash FUNCTION TABLE)` calls `FUNCTION` with the key and value
+/// of every entry, in insertion order.
+pub fn maphash<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let name = ds::car(&list);
+    let table = try_result!(hash_table(&ds::car(&ds::cdr(&list))));
+    let function = match &name {
+        Value::Symbol(symbol) | Value::QuotedSymbol(symbol) =>
+            try_result!(vm.inner_mut().get_symbol_function(&symbol.unquote())),
+        _ => None,
+    };
+    let Some(function) = function else {
+        return Err(runtime_error(format!("invalid function: {}", name.unquote()), None));
+    };
+    for (key, value) in table.entries() {
+        // builtins evaluate their arguments, functions defined with
+        // defun are given them evaluated
+        let arguments = match &function {
+            Function::Builtin { .. } => ds::list([key.quote(), value.quote()]),
+            Function::Defun { .. } => ds::list([key, value]),
+        };
+        try_result!(function.call(vm.clone(), arguments));
+    }
+    Ok(Value::Nil)
+}
+
+pub fn hash_table_count<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let table = try_result!(hash_table(&ds::car(&list)));
+    Ok(Value::unsigned_integer(table.len() as u64))
+}
+
+/// `(hash-table-keys TABLE)` lists the keys in insertion order.
+pub fn hash_table_keys<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let table = try_result!(hash_table(&ds::car(&list)));
+    Ok(Value::from_iter(table.keys()))
+}
+
+fn hash_table<'c>(value: &Value<'c>) -> Result<HashTable<'c>> {
+    match value {
+        Value::HashTable(table) => Ok(table.clone()),
+        value => Err(runtime_error(
+            format!("wrong type argument: hash-table-p, {}", value),
+            None,
+        )),
+    }
+}
+
+fn symbol_name<'c>(value: &Value<'c>) -> Option<&'c str> {
+    match value {
+        Value::Symbol(symbol) | Value::QuotedSymbol(symbol) => Some(symbol.symbol()),
+        _ => None,
+    }
+}
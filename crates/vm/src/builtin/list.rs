@@ -1,71 +1,250 @@
 
 use minilisp_data_structures as ds;
-use minilisp_data_structures::{AsCell, Quotable, Value};
+use minilisp_data_structures::{AsCell, Cell, Quotable, Value};
+use minilisp_util::{try_result, with_caller};
 use unique_pointer::UniquePointer;
 
 use crate::helpers::runtime_error;
 use crate::{Result, Context};
 
 pub fn list<'c>(
-    vm: UniquePointer<Context<'c>>,
+    mut vm: UniquePointer<Context<'c>>,
     list: Value<'c>,
 ) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
     Ok(ds::list(list))
 }
 
 pub fn cons<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    Ok(ds::pair(ds::car(&list), ds::car(&ds::cdr(&list))))
+}
+/// `(quote x)` returns `x` unevaluated, the same value `'x`
+/// evaluates to.
+pub fn quote<'c>(
     vm: UniquePointer<Context<'c>>,
     list: Value<'c>,
 ) -> Result<Value<'c>> {
-    let cell = ds::cons(ds::car(&list), &mut ds::cdr(&list).as_cell());
-    Ok(if list.is_quoted() {
-        Value::quoted_list(cell)
-    } else {
-        Value::list(cell)
+    Ok(match &ds::car(&list) {
+        Value::Symbol(symbol) => Value::quoted_symbol(symbol),
+        value => value.quote(),
     })
 }
-pub fn quote<'c>(
+/// `(function f)`, also read from `#'f`, returns `f` unevaluated:
+/// the bare symbol `f`, or the lambda form itself.
+pub fn function<'c>(
     vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    Ok(ds::car(&list).unquote())
+}
+pub fn backquote<'c>(
+    vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    quasiquote(vm, ds::car(&list), 1)
+}
+
+/// Expands the quasiquoted `value` found at nesting `depth`,
+/// evaluating `(\, x)` and splicing `(\,@ x)` only at depth 1.
+fn quasiquote<'c>(
+    mut vm: UniquePointer<Context<'c>>,
     value: Value<'c>,
+    depth: usize,
 ) -> Result<Value<'c>> {
-    Ok(match &value {
-        Value::List(_) => value.clone().quote(),
-        Value::QuotedList(_) => value.clone().quote(),
-        Value::Symbol(_) => value.clone().quote(),
-        Value::QuotedSymbol(_) => value.clone().quote(),
-        item =>
+    let cell = match &value {
+        Value::List(cell) | Value::QuotedList(cell) if !cell.is_nil() => cell.clone(),
+        _ => return Ok(value),
+    };
+    let argument = ds::car(&ds::cdr(&value));
+    match quasiquote_symbol(&ds::car(&value)) {
+        Some(",") if depth == 1 => return Ok(try_result!(vm.inner_mut().eval(argument))),
+        Some(",@") if depth == 1 =>
             return Err(runtime_error(
-                format!(
-                    "quote invoked with non-symbol and non-list: {:#?}",
-                    item
-                ),
+                format!("unquote-splicing outside of a list: {}", value),
                 None,
             )),
+        Some(symbol @ ("," | ",@")) =>
+            return Ok(ds::list([
+                Value::symbol(symbol),
+                try_result!(quasiquote(vm, argument, depth - 1)),
+            ])),
+        Some(_) =>
+            return Ok(ds::list([
+                Value::symbol("backquote"),
+                try_result!(quasiquote(vm, argument, depth + 1)),
+            ])),
+        None => {},
+    }
+    let mut elements = cell.clone().into_iter().collect::<Vec<Value<'c>>>();
+    // `(a . ,b)` reads as `(a \, b)`: the unquote is the list's tail
+    let mut unquoted_tail = None;
+    if elements.len() > 2
+        && matches!(&elements[elements.len() - 2], Value::Symbol(symbol) if symbol.symbol() == ",")
+    {
+        let argument = elements.pop().expect("argument");
+        let unquote = elements.pop().expect("unquote");
+        unquoted_tail = Some(ds::list([unquote, argument]));
+    }
+    let mut items = Vec::new();
+    for item in elements {
+        if depth == 1 && quasiquote_symbol(&ds::car(&item)) == Some(",@") {
+            match &try_result!(vm.inner_mut().eval(ds::car(&ds::cdr(&item)))) {
+                Value::List(spliced) | Value::QuotedList(spliced) =>
+                    items.extend(spliced.values()),
+                Value::Nil | Value::EmptyList | Value::EmptyQuotedList => {},
+                spliced =>
+                    return Err(runtime_error(
+                        format!("unquote-splicing of non-list: {}", spliced),
+                        None,
+                    )),
+            }
+        } else {
+            items.push(try_result!(quasiquote(vm.clone(), item, depth)));
+        }
+    }
+    let mut items = Cell::from_iter(items);
+    if let Some(dotted_tail) = unquoted_tail.or_else(|| cell.dotted_tail()) {
+        items.set_dotted_tail(try_result!(quasiquote(vm, dotted_tail, depth)));
+    }
+    Ok(if value.is_quoted() {
+        Value::quoted_list(items)
+    } else {
+        Value::list(items)
     })
 }
-pub fn backquote<'c>(
-    vm: UniquePointer<Context<'c>>,
-    list: Value<'c>,
-) -> Result<Value<'c>> {
-    Ok(list)
+
+fn quasiquote_symbol<'c>(head: &Value<'c>) -> Option<&'c str> {
+    match head {
+        Value::Symbol(symbol) => match symbol.symbol() {
+            symbol @ ("," | ",@" | "backquote") => Some(symbol),
+            _ => None,
+        },
+        _ => None,
+    }
 }
 
 pub fn car<'c>(
-    vm: UniquePointer<Context<'c>>,
+    mut vm: UniquePointer<Context<'c>>,
     list: Value<'c>,
 ) -> Result<Value<'c>> {
-    Ok(ds::car(&ds::append(list)))
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    Ok(ds::car(&ds::car(&list).unquote()))
 }
 
 pub fn cdr<'c>(
-    vm: UniquePointer<Context<'c>>,
+    mut vm: UniquePointer<Context<'c>>,
     list: Value<'c>,
 ) -> Result<Value<'c>> {
-    Ok(ds::cdr(&ds::append(list)))
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    Ok(ds::cdr(&ds::car(&list).unquote()))
 }
 pub fn append<'c>(
-    vm: UniquePointer<Context<'c>>,
+    mut vm: UniquePointer<Context<'c>>,
     list: Value<'c>,
 ) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
     Ok(ds::append(list))
 }
+
+pub fn consp<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    Ok(Value::from(ds::consp(&ds::car(&list))))
+}
+
+pub fn listp<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    Ok(Value::from(ds::listp(&ds::car(&list))))
+}
+
+pub fn proper_list_p<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    Ok(match ds::proper_list_p(&ds::car(&list)) {
+        Some(length) => Value::unsigned_integer(length as u64),
+        None => Value::nil(),
+    })
+}
+
+/// `(setcar CELL NEWCAR)` replaces the car of `CELL` in place.
+pub fn setcar<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let cell = ds::car(&list);
+    match ds::setcar(&cell, ds::car(&ds::cdr(&list)).unquote()) {
+        Some(value) => Ok(value),
+        None => Err(runtime_error(format!("wrong type argument: consp, {}", cell), None)),
+    }
+}
+
+/// `(setcdr CELL NEWCDR)` replaces the cdr of `CELL` in place.
+pub fn setcdr<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let cell = ds::car(&list);
+    match ds::setcdr(&cell, ds::car(&ds::cdr(&list)).unquote()) {
+        Some(value) => Ok(value),
+        None => Err(runtime_error(format!("wrong type argument: consp, {}", cell), None)),
+    }
+}
+
+/// `(nconc &rest LISTS)` concatenates `LISTS` in place.
+pub fn nconc<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let lists = list.values().iter().map(Quotable::unquote).collect::<Vec<_>>();
+    match ds::nconc(lists.clone()) {
+        Some(value) => Ok(value),
+        None => Err(runtime_error(
+            format!(
+                "wrong type argument: consp, {}",
+                lists.iter().find(|list| !ds::listp(list)).unwrap_or(&Value::Nil)
+            ),
+            None,
+        )),
+    }
+}
+
+/// `(nreverse LIST)` reverses `LIST` in place.
+pub fn nreverse<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let sequence = ds::car(&list).unquote();
+    match ds::nreverse(&sequence) {
+        Some(value) => Ok(value),
+        None => Err(runtime_error(format!("wrong type argument: listp, {}", sequence), None)),
+    }
+}
+
+/// `(delq ELT LIST)` removes the elements of `LIST` `eq` to `ELT` in
+/// place.
+pub fn delq<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let sequence = ds::car(&ds::cdr(&list)).unquote();
+    match ds::delq(&ds::car(&list), &sequence) {
+        Some(value) => Ok(value),
+        None => Err(runtime_error(format!("wrong type argument: listp, {}", sequence), None)),
+    }
+}
@@ -1,115 +1,90 @@
-//BinaryHeap;
-
-use minilisp_data_structures::{
-    append, car, cdr, AsFloat, AsInteger, AsUnsignedInteger, Cell, Value,
-};
-use minilisp_util::{dbg, try_result, with_caller};
+use minilisp_data_structures::{ArithmeticError, Number, Value};
+use minilisp_util::{try_result, with_caller};
 use unique_pointer::UniquePointer;
 
-// use crate::helpers::{
-//     unpack_float_items, unpack_integer_items, unpack_unsigned_integer_items,
-// };
-use crate::{
-    impl_arithmetic_operation, runtime_error, unfold_numeric_values_from_cdr, Result,
-    Context,
-};
+use crate::helpers::arithmetic_error;
+use crate::{runtime_error, Context, Result};
 
-impl_arithmetic_operation!(+ add);
-impl_arithmetic_operation!(-sub);
-impl_arithmetic_operation!(*mul);
-impl_arithmetic_operation!(/ div);
+/// `(+ &rest NUMBERS)` adds numbers of any type, promoting them along
+/// the numeric tower of [`Number`]. `(+)` is `0`.
+pub fn add<'c>(vm: UniquePointer<Context<'c>>, list: Value<'c>) -> Result<Value<'c>> {
+    let operands = try_result!(numbers(vm, list));
+    Ok(try_result!(fold("+", Number::UnsignedInteger(0), operands, Number::checked_add)).into())
+}
 
-#[macro_export]
-macro_rules! unfold_numeric_values_from_cdr {
-    (
-        $operator:expr,
-        $lifetime:lifetime,
-        $vm:expr,
-        $list:expr,Value::
-        $numeric_variant:ident,
-        $as_value_fragment_name:ident,
-        $is_value_fragment_name:ident
-        $(,)?
-    ) => {{
-        let mut operands = Cell::nil();
-        for value in cdr(&$list).into_iter() {
-            if value.$is_value_fragment_name() {
-                operands.add(&Cell::from(value));
-            } else if value.is_list() {
-                operands.add(&Cell::from(try_result!($vm.inner_mut().eval(value))));
-            } else if value.is_symbol() {
-                operands.add(&Cell::from(try_result!($vm.inner_mut().eval(value))));
-            } else {
-                return Err(with_caller!(runtime_error(
-                    format!(
-                        "called with unexpected, non-numerical value: {:#?}",
-                        value
-                    ),
-                    None
-                )));
-            }
-        }
-        operands.into_iter().map(|value|value.$as_value_fragment_name()) //.collect::<Value<$lifetime>>()
-    }};
+/// `(* &rest NUMBERS)`, `1` without arguments.
+pub fn mul<'c>(vm: UniquePointer<Context<'c>>, list: Value<'c>) -> Result<Value<'c>> {
+    let operands = try_result!(numbers(vm, list));
+    Ok(try_result!(fold("*", Number::UnsignedInteger(1), operands, Number::checked_mul)).into())
+}
+
+/// `(- &optional NUMBER &rest NUMBERS)` subtracts `NUMBERS` from
+/// `NUMBER`, or negates `NUMBER` when it is the only argument.
+pub fn sub<'c>(vm: UniquePointer<Context<'c>>, list: Value<'c>) -> Result<Value<'c>> {
+    let mut operands = try_result!(numbers(vm, list));
+    if operands.is_empty() {
+        return Ok(Value::unsigned_integer(0u32));
+    }
+    let first = operands.remove(0);
+    if operands.is_empty() {
+        return Ok((-first).into());
+    }
+    Ok(try_result!(fold("-", first, operands, Number::checked_sub)).into())
 }
 
-#[macro_export]
-macro_rules! impl_arithmetic_operation {
-    (
-        $operator:tt
-            $function_name:ident
-    ) => {
-        pub fn $function_name<'c>(
-            mut vm: UniquePointer<Context<'c>>,
-            list: Value<'c>,
-        ) -> Result<Value<'c>> {
-            let argcount = list.len();
-            if argcount < 2 {
+/// `(/ NUMBER &rest DIVISORS)` divides `NUMBER` by each of `DIVISORS`,
+/// or takes the reciprocal of `NUMBER` when it is the only argument.
+/// Integers that do not divide evenly make a rational.
+pub fn div<'c>(vm: UniquePointer<Context<'c>>, list: Value<'c>) -> Result<Value<'c>> {
+    let mut operands = try_result!(numbers(vm, list));
+    if operands.is_empty() {
+        return Err(with_caller!(runtime_error(
+            "wrong number of arguments: /, 0".to_string(),
+            None
+        )));
+    }
+    let first = match operands.len() {
+        1 => Number::UnsignedInteger(1),
+        _ => operands.remove(0),
+    };
+    Ok(try_result!(fold("/", first, operands, Number::checked_div)).into())
+}
+
+/// Applies `operation` to `first` and each of `operands` in turn,
+/// failing with the operands of the first operation that fails.
+fn fold(
+    operator: &'static str,
+    first: Number,
+    operands: Vec<Number>,
+    operation: fn(Number, Number) -> std::result::Result<Number, ArithmeticError>,
+) -> Result<Number> {
+    let mut result = first;
+    for operand in operands {
+        result = match operation(result.clone(), operand.clone()) {
+            Ok(number) => number,
+            Err(error) => return Err(arithmetic_error(error, operator, &[&result, &operand])),
+        };
+    }
+    Ok(result)
+}
+
+/// Evaluates the arguments in `list`, which must all be numbers.
+pub(crate) fn numbers<'c>(mut vm: UniquePointer<Context<'c>>, list: Value<'c>) -> Result<Vec<Number>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let arguments = match &list {
+        Value::List(cell) | Value::QuotedList(cell) => cell.values(),
+        _ => Vec::new(),
+    };
+    let mut operands = Vec::with_capacity(arguments.len());
+    for argument in arguments {
+        match Number::from_value(&argument) {
+            Some(number) => operands.push(number),
+            None =>
                 return Err(with_caller!(runtime_error(
-                    format!(
-                        "{:#?} takes at least 2 arguments, got: {}",
-                        stringify!($operator),
-                        argcount
-                    ),
-                    None
-                )));
-            }
-            match &car(&list) {
-                Value::UnsignedInteger(first)=> {
-                    let first = first.clone();
-                    let operands = unfold_numeric_values_from_cdr!("$operator", 'c, vm, list, Value::UnsignedInteger, as_unsigned_integer, is_unsigned_integer);
-                    Ok(Value::UnsignedInteger(operands.fold(first, |lhs, rhs| lhs $operator rhs)))
-                },
-                Value::Integer(first)=> {
-                    let first = first.clone();
-                    let operands = unfold_numeric_values_from_cdr!("$operator", 'c, vm, list, Value::Integer, as_integer, is_integer);
-                    Ok(Value::Integer(operands.fold(first, |lhs, rhs| lhs $operator rhs)))
-                },
-                Value::Float(first)=> {
-                    let first = first.clone();
-                    let operands = unfold_numeric_values_from_cdr!("$operator", 'c, vm, list, Value::Float, as_float, is_float);
-                    Ok(Value::Float(operands.fold(first, |lhs, rhs| lhs $operator rhs)))
-                },
-                Value::Symbol(sym) | Value::QuotedSymbol(sym) => {
-                    let first = try_result!(vm.inner_mut().eval(Value::from(sym)));
-                    Ok(try_result!($function_name(vm, append([first, cdr(&list)]))))
-                },
-                Value::List(tmp) | Value::QuotedList(tmp) => {
-                    dbg!(&vm.inner_ref(), &tmp);
-                    Ok(try_result!($function_name(vm.clone(), append([try_result!(vm.inner_mut().eval(car(&list))), cdr(&list)]))))
-                },
-                value => Err(with_caller!(runtime_error(
-                    format!(
-                        "{:#?} called with non-numerical value: {:#?}",
-                        stringify!($operator),
-                        value
-                    ),
+                    format!("wrong type argument: number-or-marker-p, {}", argument),
                     None
                 ))),
-                _ => {
-                    unreachable!()
-                },
-            }
         }
-    };
+    }
+    Ok(operands)
 }
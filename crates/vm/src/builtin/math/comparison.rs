@@ -0,0 +1,39 @@
+use std::cmp::Ordering;
+
+use minilisp_data_structures::Value;
+use minilisp_util::{try_result, with_caller};
+use unique_pointer::UniquePointer;
+
+use crate::builtin::equality::boolean;
+use crate::builtin::math::arithmetic::numbers;
+use crate::{runtime_error, Context, Result};
+
+/// `(= NUMBER &rest NUMBERS)` is `t` when all the numbers have the
+/// same value, whatever their types: `(= 1 1.0)` is `t`.
+pub fn num_eq<'c>(vm: UniquePointer<Context<'c>>, list: Value<'c>) -> Result<Value<'c>> {
+    let operands = try_result!(numbers(vm, list));
+    if operands.is_empty() {
+        return Err(with_caller!(runtime_error(
+            "wrong number of arguments: =, 0".to_string(),
+            None
+        )));
+    }
+    Ok(boolean(
+        operands
+            .windows(2)
+            .all(|pair| pair[0].compare(&pair[1]) == Some(Ordering::Equal)),
+    ))
+}
+
+/// `(/= A B)` is `t` when the numbers `A` and `B` have different
+/// values.
+pub fn num_ne<'c>(vm: UniquePointer<Context<'c>>, list: Value<'c>) -> Result<Value<'c>> {
+    let operands = try_result!(numbers(vm, list));
+    let [a, b] = operands.as_slice() else {
+        return Err(with_caller!(runtime_error(
+            format!("wrong number of arguments: /=, {}", operands.len()),
+            None
+        )));
+    };
+    Ok(boolean(a.compare(b) != Some(Ordering::Equal)))
+}
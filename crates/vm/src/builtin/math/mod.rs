@@ -1 +1,2 @@
 pub mod arithmetic;
+pub mod comparison;
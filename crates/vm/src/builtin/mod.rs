@@ -1,8 +1,12 @@
+pub mod equality;
+pub mod hash_table;
 pub mod identity;
 pub mod list;
 pub mod math;
+pub mod sequence;
 pub mod state;
 pub mod string;
+pub mod symbol;
 
 use minilisp_data_structures::Value;
 use unique_pointer::UniquePointer;
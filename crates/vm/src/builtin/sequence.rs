@@ -0,0 +1,105 @@
+use minilisp_data_structures as ds;
+use minilisp_data_structures::{Quotable, Value, Vector};
+use minilisp_util::{try_result, with_caller};
+use unique_pointer::UniquePointer;
+
+use crate::helpers::runtime_error;
+use crate::{Result, Context};
+
+pub fn length<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let sequence = ds::car(&list);
+    let length = match &sequence {
+        Value::String(string) => string.chars().count(),
+        Value::Vector(vector) => vector.len(),
+        Value::List(cell) | Value::QuotedList(cell) if cell.is_proper() => cell.len(),
+        Value::Nil | Value::EmptyList | Value::EmptyQuotedList => 0,
+        _ => return Err(wrong_type_argument("sequencep", &sequence)),
+    };
+    Ok(Value::unsigned_integer(length as u64))
+}
+
+pub fn make_vector<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let length = try_result!(index(&ds::car(&list)));
+    let init = ds::car(&ds::cdr(&list)).unquote();
+    Ok(Value::vector(std::iter::repeat_n(init, length)))
+}
+
+pub fn vconcat<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let mut items = Vec::<Value<'c>>::new();
+    for sequence in list.into_iter() {
+        match &sequence {
+            Value::String(string) => items.extend(string.chars().map(Value::char)),
+            Value::Vector(vector) => items.extend(vector.values()),
+            Value::List(cell) | Value::QuotedList(cell) if cell.is_proper() =>
+                items.extend(cell.values().iter().map(Value::unquote)),
+            Value::Nil | Value::EmptyList | Value::EmptyQuotedList => {},
+            _ => return Err(wrong_type_argument("sequencep", &sequence)),
+        }
+    }
+    Ok(Value::vector(items))
+}
+
+pub fn aref<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let array = ds::car(&list);
+    let position = ds::car(&ds::cdr(&list));
+    let index = try_result!(index(&position));
+    let item = match &array {
+        Value::Vector(vector) => vector.get(index),
+        Value::String(string) => string.chars().nth(index).map(Value::char),
+        _ => return Err(wrong_type_argument("arrayp", &array)),
+    };
+    item.ok_or_else(|| args_out_of_range(&array, &position))
+}
+
+pub fn aset<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let array = ds::car(&list);
+    let position = ds::car(&ds::cdr(&list));
+    let value = ds::car(&ds::cdr(&ds::cdr(&list))).unquote();
+    let index = try_result!(index(&position));
+    match &array {
+        Value::Vector(vector) => match vector.set(index, value.clone()) {
+            Some(_) => Ok(value),
+            None => Err(args_out_of_range(&array, &position)),
+        },
+        _ => Err(wrong_type_argument("vectorp", &array)),
+    }
+}
+
+fn index<'c>(value: &Value<'c>) -> Result<usize> {
+    match value {
+        Value::UnsignedInteger(index) => Ok(**index as usize),
+        Value::Integer(index) if **index >= 0 => Ok(**index as usize),
+        value => Err(wrong_type_argument("wholenump", value)),
+    }
+}
+
+fn wrong_type_argument<'c>(predicate: &str, value: &Value<'c>) -> crate::Error {
+    runtime_error(
+        format!("wrong type argument: {}, {}", predicate, value),
+        None,
+    )
+}
+
+fn args_out_of_range<'c>(array: &Value<'c>, index: &Value<'c>) -> crate::Error {
+    runtime_error(format!("args out of range: {}, {}", array, index), None)
+}
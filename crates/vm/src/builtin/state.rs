@@ -3,6 +3,7 @@
 use minilisp_data_structures::{
     car, cdr, AsSymbol, Value,
 };
+use minilisp_util::{try_result, with_caller};
 use unique_pointer::UniquePointer;
 
 use crate::helpers::runtime_error;
@@ -23,14 +24,19 @@ pub fn setq<'c>(
             None,
         ));
     }
-    let head = car(&list);
-    if !head.is_symbol() {
-        return Err(runtime_error(
-            format!("setq invoked with non-symbol: {:#?}", head),
-            None,
-        ));
+    let mut value = Value::nil();
+    let mut pairs = list.into_iter();
+    while let Some(head) = pairs.next() {
+        if !head.is_symbol() {
+            return Err(runtime_error(
+                format!("setq invoked with non-symbol: {:#?}", head),
+                None,
+            ));
+        }
+        value = try_result!(vm.inner_mut().eval(pairs.next().unwrap_or_default()));
+        try_result!(vm.set_global(&head.as_symbol(), &Sym::Value(value.clone())));
     }
-    Ok(vm.set_global(&head.as_symbol(), &Sym::Value(cdr(&list)))?)
+    Ok(value)
 }
 
 
@@ -1,14 +1,19 @@
  //BinaryHeap;
 
-use minilisp_data_structures::Value;
+use minilisp_data_structures as ds;
+use minilisp_data_structures::{Printer, Quotable, Symbol, Value};
+use minilisp_parser::parse_program;
+use minilisp_util::{try_result, with_caller};
 use unique_pointer::UniquePointer;
 
-use crate::{Result, Context};
+use crate::helpers::runtime_error;
+use crate::{Context, Result, Sym};
 
 pub fn print<'c>(
-    vm: UniquePointer<Context<'c>>,
+    mut vm: UniquePointer<Context<'c>>,
     list: Value<'c>,
 ) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
     println!(
         "{}",
         list.clone()
@@ -19,3 +24,95 @@ pub fn print<'c>(
     );
     Ok(list)
 }
+
+/// Prints its argument readably, without a newline, and returns it.
+pub fn prin1<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let object = ds::car(&list).unquote();
+    print!("{}", try_result!(printer(&mut vm, true)).print(&object));
+    Ok(object)
+}
+
+/// Prints its argument for humans, without a newline, and returns
+/// it.
+pub fn princ<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let object = ds::car(&list).unquote();
+    print!("{}", try_result!(printer(&mut vm, false)).print(&object));
+    Ok(object)
+}
+
+pub fn prin1_to_string<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let printer = try_result!(printer(&mut vm, true));
+    Ok(Value::string(printer.print(&ds::car(&list).unquote())))
+}
+
+/// The printer of `prin1` or `princ`, labelling shared and circular
+/// structure when `print-circle` is non-nil.
+fn printer<'c>(vm: &mut UniquePointer<Context<'c>>, readable: bool) -> Result<Printer> {
+    let circle = match try_result!(vm.inner_mut().get_symbol(&Symbol::new("print-circle"))) {
+        Sym::Value(value) => !value.is_nil(),
+        Sym::Function(_) => true,
+    };
+    Ok(Printer { readable, circle })
+}
+
+/// `(read STRING)` reads the first form of `STRING`, `(read)` reads
+/// the next form from the input of the virtual machine.
+pub fn read<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    match &ds::car(&list) {
+        Value::String(string) => match try_result!(parse_program(string)).into_iter().next() {
+            Some(form) => Ok(form.value.to_owned()),
+            None => Err(runtime_error("end of file during parsing".to_string(), None)),
+        },
+        Value::Nil | Value::EmptyList | Value::EmptyQuotedList =>
+            vm.inner_mut().vm.inner_mut().read(),
+        value => Err(runtime_error(
+            format!("wrong type argument: stringp, {}", value),
+            None,
+        )),
+    }
+}
+
+pub fn char_to_string<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    match ds::car(&list) {
+        Value::Char(c) => Ok(Value::string(c)),
+        value => Err(runtime_error(
+            format!("wrong type argument: characterp, {}", value),
+            None,
+        )),
+    }
+}
+
+pub fn string_to_char<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    match &ds::car(&list) {
+        Value::String(string) =>
+            Ok(Value::char(string.chars().next().unwrap_or('\0'))),
+        value => Err(runtime_error(
+            format!("wrong type argument: stringp, {}", value),
+            None,
+        )),
+    }
+}
@@ -0,0 +1,118 @@
+use minilisp_data_structures as ds;
+use minilisp_data_structures::{Quotable, Symbol, Value};
+use minilisp_util::{try_result, with_caller};
+use unique_pointer::UniquePointer;
+
+use crate::helpers::runtime_error;
+use crate::{Context, Result};
+
+/// `(intern NAME)` returns the symbol named by the string `NAME`.
+pub fn intern<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    match &ds::car(&list) {
+        Value::String(name) => Ok(Value::symbol(name.as_str())),
+        value => Err(runtime_error(
+            format!("wrong type argument: stringp, {}", value),
+            None,
+        )),
+    }
+}
+
+/// `(intern-soft NAME)` returns the symbol named by `NAME`, a string
+/// or a symbol, when such a symbol was ever built and nil otherwise.
+pub fn intern_soft<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let name = ds::car(&list);
+    let name = match &name {
+        Value::String(name) => name.as_str(),
+        Value::Symbol(symbol) | Value::QuotedSymbol(symbol) => symbol.symbol(),
+        value => {
+            return Err(runtime_error(
+                format!("wrong type argument: stringp, {}", value),
+                None,
+            ))
+        },
+    };
+    Ok(Symbol::interned(name).map(Value::Symbol).unwrap_or(Value::Nil))
+}
+
+/// `(symbol-name SYMBOL)` returns the name of `SYMBOL` as a string.
+pub fn symbol_name<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    match &ds::car(&list) {
+        Value::Symbol(symbol) | Value::QuotedSymbol(symbol) =>
+            Ok(Value::string(symbol.symbol())),
+        Value::Nil => Ok(Value::string("nil")),
+        Value::T => Ok(Value::string("t")),
+        value => Err(runtime_error(
+            format!("wrong type argument: symbolp, {}", value),
+            None,
+        )),
+    }
+}
+
+/// `(symbol-plist SYMBOL)` returns the property list of `SYMBOL`.
+pub fn symbol_plist<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let symbol = try_result!(symbol(&ds::car(&list)));
+    Ok(symbol.plist())
+}
+
+/// `(setplist SYMBOL PLIST)` replaces the property list of `SYMBOL`
+/// with `PLIST` and returns `PLIST`.
+pub fn setplist<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let symbol = try_result!(symbol(&ds::car(&list)));
+    let plist = ds::car(&ds::cdr(&list)).unquote();
+    symbol.set_plist(&plist);
+    Ok(plist)
+}
+
+/// `(put SYMBOL PROPERTY VALUE)` sets the `PROPERTY` of `SYMBOL` to
+/// `VALUE` and returns `VALUE`.
+pub fn put<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let symbol = try_result!(symbol(&ds::car(&list)));
+    let value = ds::car(&ds::cdr(&ds::cdr(&list))).unquote();
+    symbol.put(&ds::car(&ds::cdr(&list)), &value);
+    Ok(value)
+}
+
+/// `(get SYMBOL PROPERTY)` returns the `PROPERTY` of `SYMBOL`, nil
+/// when it has none.
+pub fn get<'c>(
+    mut vm: UniquePointer<Context<'c>>,
+    list: Value<'c>,
+) -> Result<Value<'c>> {
+    let list = try_result!(vm.inner_mut().eval_list_as_items(list));
+    let symbol = try_result!(symbol(&ds::car(&list)));
+    Ok(symbol.get(&ds::car(&ds::cdr(&list))))
+}
+
+fn symbol<'c>(value: &Value<'c>) -> Result<Symbol<'c>> {
+    match value {
+        Value::Symbol(symbol) | Value::QuotedSymbol(symbol) => Ok(symbol.unquote()),
+        value => Err(runtime_error(
+            format!("wrong type argument: symbolp, {}", value.unquote()),
+            None,
+        )),
+    }
+}
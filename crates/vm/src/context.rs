@@ -15,6 +15,8 @@ use crate::{
 pub struct Context<'c> {
     pub(crate) symbols: SymbolTable<'c>,
     pub(crate) vm: UniquePointer<VirtualMachine<'c>>,
+    /// forms whose evaluation failed, innermost first
+    pub(crate) failed_forms: Vec<Value<'c>>,
 }
 
 impl<'c> Debug for Context<'c> {
@@ -35,7 +37,11 @@ impl<'c> Context<'c> {
         symbols: SymbolTable<'c>,
     ) -> Context<'c> {
         // info!("VirtualMachine.new", 178);
-        let context = Context { symbols, vm };
+        let context = Context {
+            symbols,
+            vm,
+            failed_forms: Vec::new(),
+        };
         // dbg!(&context);
         context
     }
@@ -94,24 +100,23 @@ impl<'c> Context<'c> {
         match try_result!(self.get_symbol_function(sym)) {
             Some(function) => {
                 // dbg!(&sym, &list);
+                let list = match function {
+                    Function::Defun { .. } => try_result!(self.eval_list_as_items(list)),
+                    Function::Builtin { .. } => list,
+                };
                 let result = function.call(vm, list);
                 match result {
-                    Ok(item) => Ok(self.eval(item)?),
+                    Ok(item) => Ok(item),
                     Err(error) => Err(runtime_error(
                         format!("Failed to evaluate function {:#?}: {}", sym, error),
                         Some(with_caller!(error)),
                     )),
                 }
             },
-            None => Ok(Value::from({
-                let mut cell = Cell::nil();
-                cell.push_value(Value::from(sym));
-                // dbg!(&sym, &list);
-                for item in list.into_iter() {
-                    cell.push_value(item);
-                }
-                cell
-            })),
+            None => Ok(Value::from(Cell::cons(
+                Value::from(sym),
+                Value::list(Cell::from_iter(list)),
+            ))),
         }
     }
 
@@ -127,15 +132,28 @@ impl<'c> Context<'c> {
             return Ok(item);
         }
         // dbg!(&self, &item);
-        match &item {
-            Value::List(_) | Value::QuotedList(_) =>
-                Ok(try_result!(self.eval_list(item))),
+        let result = match &item {
+            Value::Symbol(symbol) if symbol.is_keyword() => return Ok(item.clone()),
+            Value::List(_) | Value::QuotedList(_) => self.eval_list(item.clone()),
             Value::Symbol(symbol) | Value::QuotedSymbol(symbol) =>
-                Ok(try_result!(self.eval_symbol(symbol, cdr(&item)))),
-            value => Ok(value.clone()),
+                self.eval_symbol(symbol, cdr(&item)),
+            value => return Ok(value.clone()),
+        };
+        match result {
+            Ok(value) => Ok(value),
+            Err(error) => {
+                self.failed_forms.push(item);
+                Err(with_caller!(error))
+            },
         }
     }
 
+    /// Forms whose evaluation failed in this context, innermost
+    /// first.
+    pub fn failed_forms(&self) -> &[Value<'c>] {
+        &self.failed_forms
+    }
+
     pub fn set_global(
         &mut self,
         sym: &Symbol<'c>,
@@ -175,18 +193,30 @@ impl<'c> Context<'c> {
                     Ok(try_result!(self.eval_symbol_function(sym, cdr(&list))))
                 },
                 Value::List(_) | Value::QuotedList(_) => {
-                    let mut cell = Cell::nil();
+                    let mut items = Vec::new();
                     for item in list.clone().into_iter() {
-                        // dbg!(&item);
-                        cell.push_value(try_result!(self.eval(item.clone())));
+                        items.push(try_result!(self.eval(item.clone())));
                     }
-                    Ok(Value::List(cell))
+                    Ok(Value::List(Cell::from_iter(items)))
                 },
                 _ => Ok(list),
             }
         }
     }
 
+    /// Evaluates each item of `list` and returns a list with the
+    /// results, in order.
+    pub fn eval_list_as_items(&mut self, list: Value<'c>) -> Result<Value<'c>> {
+        if list.is_empty() {
+            return Ok(list);
+        }
+        let mut items = Vec::new();
+        for item in list.into_iter() {
+            items.push(try_result!(self.eval(item)));
+        }
+        Ok(Value::list(Cell::from_iter(items)))
+    }
+
     pub fn eval_symbol(
         &mut self,
         sym: &Symbol<'c>,
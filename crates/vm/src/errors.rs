@@ -6,6 +6,8 @@ pub enum ErrorType {
     FormatError,
     ParserError,
     RuntimeError,
+    ArithError,
+    OverflowError,
 }
 impl std::fmt::Display for ErrorType {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -17,6 +19,8 @@ impl std::fmt::Display for ErrorType {
                 Self::FormatError => "FormatError",
                 Self::ParserError => "ParserError",
                 Self::RuntimeError => "RuntimeError",
+                Self::ArithError => "ArithError",
+                Self::OverflowError => "OverflowError",
             }
         )
     }
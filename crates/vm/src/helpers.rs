@@ -1,3 +1,4 @@
+use minilisp_data_structures::{prin1, ArithmeticError, Number, Value};
 use minilisp_util::with_caller;
 
 use crate::{Error, ErrorType};
@@ -5,3 +6,17 @@ use crate::{Error, ErrorType};
 pub fn runtime_error(message: String, previous: Option<Error>) -> Error {
     with_caller!(Error::with_previous_error(message, ErrorType::RuntimeError, previous))
 }
+
+/// The `arith-error` or `overflow-error` of applying `operator` to
+/// `operands`, such as `arith-error: division by zero: (/ 1 0)`.
+pub fn arithmetic_error(error: ArithmeticError, operator: &'static str, operands: &[&Number]) -> Error {
+    let ty = match error {
+        ArithmeticError::Overflow => ErrorType::OverflowError,
+        ArithmeticError::DivisionByZero | ArithmeticError::NotANumber => ErrorType::ArithError,
+    };
+    let operation = Value::from_iter(
+        std::iter::once(Value::symbol(operator))
+            .chain(operands.iter().map(|operand| Value::from((*operand).clone()))),
+    );
+    with_caller!(Error::new(format!("{}: {}: {}", error.name(), error, prin1(&operation)), ty))
+}
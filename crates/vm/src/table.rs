@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use minilisp_data_structures::{AsValue, Cell, Quotable, Symbol, Value};
@@ -6,11 +6,11 @@ use minilisp_util::{try_result, unexpected, with_caller};
 use unique_pointer::UniquePointer;
 
 use crate::{
-    builtin, info, warn, BuiltinFunction, Context, Function, Result, Sym,
+    builtin, info, runtime_error, warn, BuiltinFunction, Context, Function, Result, Sym,
     VirtualMachine,
 };
 
-pub type SymTable<'c> = BTreeMap<Symbol<'c>, Sym<'c>>;
+pub type SymTable<'c> = HashMap<Symbol<'c>, Sym<'c>>;
 
 #[derive(Clone)]
 pub struct SymbolTable<'c> {
@@ -55,15 +55,65 @@ impl<'c> SymbolTable<'c> {
         register_builtin_function(&mut globals, "cons", builtin::list::cons);
         register_builtin_function(&mut globals, "list", builtin::list::list);
         register_builtin_function(&mut globals, "append", builtin::list::append);
+        register_builtin_function(&mut globals, "consp", builtin::list::consp);
+        register_builtin_function(&mut globals, "listp", builtin::list::listp);
+        register_builtin_function(&mut globals, "proper-list-p", builtin::list::proper_list_p);
+        register_builtin_function(&mut globals, "setcar", builtin::list::setcar);
+        register_builtin_function(&mut globals, "setcdr", builtin::list::setcdr);
+        register_builtin_function(&mut globals, "nconc", builtin::list::nconc);
+        register_builtin_function(&mut globals, "nreverse", builtin::list::nreverse);
+        register_builtin_function(&mut globals, "delq", builtin::list::delq);
         register_builtin_function(&mut globals, "quote", builtin::list::quote);
+        register_builtin_function(&mut globals, "function", builtin::list::function);
         register_builtin_function(&mut globals, "print", builtin::string::print);
+        register_builtin_function(&mut globals, "prin1", builtin::string::prin1);
+        register_builtin_function(&mut globals, "princ", builtin::string::princ);
+        register_builtin_function(&mut globals, "prin1-to-string", builtin::string::prin1_to_string);
+        register_builtin_function(&mut globals, "read", builtin::string::read);
+        register_builtin_function(&mut globals, "char-to-string", builtin::string::char_to_string);
+        register_builtin_function(&mut globals, "string-to-char", builtin::string::string_to_char);
         register_builtin_function(&mut globals, "backquote", builtin::list::backquote);
 
+        // printer variables
+        register_variable(&mut globals, "print-circle", Value::Nil);
+
+        // equality functions
+        register_builtin_function(&mut globals, "eq", builtin::equality::eq);
+        register_builtin_function(&mut globals, "eql", builtin::equality::eql);
+        register_builtin_function(&mut globals, "equal", builtin::equality::equal);
+
+        // symbol functions
+        register_builtin_function(&mut globals, "intern", builtin::symbol::intern);
+        register_builtin_function(&mut globals, "intern-soft", builtin::symbol::intern_soft);
+        register_builtin_function(&mut globals, "symbol-name", builtin::symbol::symbol_name);
+        register_builtin_function(&mut globals, "symbol-plist", builtin::symbol::symbol_plist);
+        register_builtin_function(&mut globals, "setplist", builtin::symbol::setplist);
+        register_builtin_function(&mut globals, "put", builtin::symbol::put);
+        register_builtin_function(&mut globals, "get", builtin::symbol::get);
+
+        // sequence functions
+        register_builtin_function(&mut globals, "length", builtin::sequence::length);
+        register_builtin_function(&mut globals, "make-vector", builtin::sequence::make_vector);
+        register_builtin_function(&mut globals, "vconcat", builtin::sequence::vconcat);
+        register_builtin_function(&mut globals, "aref", builtin::sequence::aref);
+        register_builtin_function(&mut globals, "aset", builtin::sequence::aset);
+
+        // hash table functions
+        register_builtin_function(&mut globals, "make-hash-table", builtin::hash_table::make_hash_table);
+        register_builtin_function(&mut globals, "gethash", builtin::hash_table::gethash);
+        register_builtin_function(&mut globals, "puthash", builtin::hash_table::puthash);
+        register_builtin_function(&mut globals, "remhash", builtin::hash_table::remhash);
+        register_builtin_function(&mut globals, "maphash", builtin::hash_table::maphash);
+        register_builtin_function(&mut globals, "hash-table-count", builtin::hash_table::hash_table_count);
+        register_builtin_function(&mut globals, "hash-table-keys", builtin::hash_table::hash_table_keys);
+
         // arithmetic functions
         register_builtin_function(&mut globals, "*", builtin::math::arithmetic::mul);
         register_builtin_function(&mut globals, "+", builtin::math::arithmetic::add);
         register_builtin_function(&mut globals, "-", builtin::math::arithmetic::sub);
         register_builtin_function(&mut globals, "/", builtin::math::arithmetic::div);
+        register_builtin_function(&mut globals, "=", builtin::math::comparison::num_eq);
+        register_builtin_function(&mut globals, "/=", builtin::math::comparison::num_ne);
 
         let mut table = SymbolTable {
             globals: globals.clone(),
@@ -74,7 +124,7 @@ impl<'c> SymbolTable<'c> {
     }
 
     pub fn extend(&mut self, other: Self) {
-        self.globals.extend(other.globals.clone())
+        self.globals.extend(other.globals.clone());
     }
 
     pub fn set_global(
@@ -135,6 +185,10 @@ fn register_builtin_function<'c>(
     table.insert(Symbol::new(sym), function.clone());
 }
 
+fn register_variable<'c>(table: &mut SymTable<'c>, sym: &str, value: Value<'c>) {
+    table.insert(Symbol::new(sym), Sym::Value(value));
+}
+
 fn set_within_map<'c>(
     map: &mut SymTable<'c>,
     context: UniquePointer<Context<'c>>,
@@ -142,6 +196,9 @@ fn set_within_map<'c>(
     item: &Sym<'c>,
 ) -> Result<Value<'c>> {
     info!(format!("set_within_map {} {}", &sym, &item), 29);
+    if sym.is_keyword() && matches!(item, Sym::Value(_)) {
+        return Err(runtime_error(format!("setting-constant: {}", sym.unquote()), None));
+    }
     let previous = map.insert(sym.clone(), item.clone());
 
     Ok(match previous.unwrap_or_else(|| item.clone()) {
@@ -1,11 +1,16 @@
+use std::cell::RefCell;
 use std::collections::{BTreeMap, VecDeque};
 use std::fmt::Debug;
+use std::io::{BufRead, BufReader};
+use std::rc::Rc;
 
 use minilisp_data_structures::{
     car, cdr, AsValue, Cell, Quotable, Symbol, Value,
 };
-use minilisp_parser::parse_source;
-use minilisp_util::{try_result, unexpected, with_caller};
+use minilisp_parser::{
+    parse_program, parse_program_with_filename, parse_source, Form, Reader, SourceMap, Span,
+};
+use minilisp_util::{try_result, unexpected, with_caller, Traceback};
 use unique_pointer::UniquePointer;
 
 use crate::{
@@ -13,10 +18,17 @@ use crate::{
     SymbolTable,
 };
 
+/// Reader of the forms `read` reads, built on stdin when `read` is
+/// first called unless set before.
+type Input = Option<Reader<Box<dyn BufRead>>>;
+
 #[derive(Clone)]
 pub struct VirtualMachine<'c> {
     symbols: SymbolTable<'c>,
     stack: VecDeque<UniquePointer<Context<'c>>>,
+    spans: SourceMap<'c>,
+    /// where `read` reads forms from, shared between clones
+    input: Rc<RefCell<Input>>,
 }
 
 impl<'c> Debug for VirtualMachine<'c> {
@@ -39,11 +51,17 @@ impl<'c> VirtualMachine<'c> {
         let vm = VirtualMachine {
             symbols: SymbolTable::new(),
             stack: VecDeque::new(),
+            spans: SourceMap::new(),
+            input: Rc::new(RefCell::new(None)),
         };
          // dbg!(&vm);
         vm
     }
 
+    pub fn symbols(&self) -> &SymbolTable<'c> {
+        &self.symbols
+    }
+
     pub(crate) fn push_context(&mut self) -> UniquePointer<Context<'c>> {
          // info!("VirtualMachine.push_context", 178);
         let context = UniquePointer::<Context<'c>>::from(Context::new(
@@ -71,6 +89,95 @@ impl<'c> VirtualMachine<'c> {
         Ok(value)
     }
 
+    /// Evaluates every top-level form of `string` in order and
+    /// returns the value of the last one, or `nil` when there are no
+    /// forms.
+    pub fn eval_program(&mut self, string: &'c str) -> Result<Value<'c>> {
+        let mut value = Value::nil();
+        for form in try_result!(parse_program(string)) {
+            value = try_result!(self.eval_form(form));
+        }
+        Ok(value)
+    }
+
+    /// Like [`VirtualMachine::eval_program`] but reports `filename`
+    /// in the location of errors.
+    pub fn eval_program_with_filename(
+        &mut self,
+        string: &'c str,
+        filename: &'c str,
+    ) -> Result<Value<'c>> {
+        let mut value = Value::nil();
+        for form in try_result!(parse_program_with_filename(string, Some(filename))) {
+            value = try_result!(self.eval_form(form));
+        }
+        Ok(value)
+    }
+
+    /// Evaluates a parsed [`Form`] so that a runtime error renders the
+    /// source excerpt of the innermost form that failed.
+    ///
+    /// The spans of the form are kept as long as its lists are alive,
+    /// as the body of a function defined by the form is, and dropped
+    /// once nothing refers to them anymore.
+    pub fn eval_form(&mut self, form: Form<'c>) -> Result<Value<'c>> {
+        let Form { value, span, spans } = form;
+        self.spans.extend(spans);
+        let mut context = self.push_context();
+        let result = context.eval(value);
+        self.update_symbols();
+        let result = match result {
+            Ok(value) => Ok(value),
+            Err(error) => {
+                let span = self.failure_span(context.failed_forms()).unwrap_or(span);
+                Err(runtime_error(
+                    format!("{}\n{}", error.message(), span.highlight_input(4)),
+                    Some(error),
+                ))
+            },
+        };
+        self.spans.prune();
+        result
+    }
+
+    /// The span of the innermost of `failed_forms` that has one: a
+    /// list by its identity, an atom as an element of the list whose
+    /// evaluation failed with it.
+    fn failure_span(&self, failed_forms: &[Value<'c>]) -> Option<Span<'c>> {
+        failed_forms
+            .iter()
+            .enumerate()
+            .find_map(|(index, value)| {
+                let list = failed_forms[index..].iter().find(|form| form.is_list())?;
+                self.spans.get_in(value, list)
+            })
+            .cloned()
+    }
+
+    /// Spans of the lists of evaluated forms that are still alive,
+    /// such as function bodies.
+    pub fn spans(&self) -> &SourceMap<'c> {
+        &self.spans
+    }
+
+    /// Makes `read` read forms from `input` instead of stdin.
+    pub fn set_input<R: BufRead + 'static>(&mut self, input: R) {
+        self.input = Rc::new(RefCell::new(Some(Reader::new(Box::new(input)))));
+    }
+
+    /// Reads the next form from the input of `read`, pulling no more
+    /// of it than that form needs.
+    pub fn read(&mut self) -> Result<Value<'c>> {
+        let mut input = self.input.borrow_mut();
+        let reader = input.get_or_insert_with(|| {
+            Reader::new(Box::new(BufReader::new(std::io::stdin())))
+        });
+        match try_result!(reader.read_form()) {
+            Some(form) => Ok(form.value),
+            None => Err(runtime_error("end of file during parsing".to_string(), None)),
+        }
+    }
+
     pub fn eval(&mut self, item: Value<'c>) -> Result<Value<'c>> {
         let value = try_result!(self.push_context().eval(item));
         self.update_symbols();
@@ -95,3 +202,30 @@ impl<'c> VirtualMachine<'c> {
         Ok(value)
     }
 }
+
+impl VirtualMachine<'static> {
+    /// Evaluates a copy of `form` made with [`Value::to_owned`], so
+    /// that neither the form nor the text it was read from have to
+    /// outlive the virtual machine.
+    ///
+    /// A runtime error renders the source excerpt of the whole form.
+    pub fn eval_owned(&mut self, form: Form<'_>) -> Result<Value<'static>> {
+        match self.eval(form.value.to_owned()) {
+            Ok(value) => Ok(value),
+            Err(error) => Err(runtime_error(
+                format!("{}\n{}", error.message(), form.span.highlight_input(4)),
+                Some(error),
+            )),
+        }
+    }
+
+    /// Like [`VirtualMachine::eval_program`] but `source` only has to
+    /// live for the call, see [`VirtualMachine::eval_owned`].
+    pub fn eval_source(&mut self, source: &str) -> Result<Value<'static>> {
+        let mut value = Value::nil();
+        for form in try_result!(parse_program(source)) {
+            value = try_result!(self.eval_owned(form));
+        }
+        Ok(value)
+    }
+}
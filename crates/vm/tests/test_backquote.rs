@@ -0,0 +1,63 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_data_structures::Value;
+use minilisp_vm::{Result, VirtualMachine};
+
+#[test]
+fn test_backquote_without_unquote() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string(r#"(car `(a b))"#)?, Value::symbol("a"));
+    assert_equal!(vm.eval_string(r#"`(a b)"#)?.to_string(), "(a b)");
+    Ok(())
+}
+
+#[test]
+fn test_backquote_unquote() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_program(
+        r#"
+        (setq b 2)
+        `(a ,b c)
+        "#,
+    )?;
+    assert_equal!(val.to_string(), "(a 2 c)");
+    Ok(())
+}
+
+#[test]
+fn test_backquote_unquote_splicing() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_string(r#"`(a ,@(list 1 2) c)"#)?;
+    assert_equal!(val.to_string(), "(a 1 2 c)");
+    let val = vm.eval_string(r#"`(a ,@nil c)"#)?;
+    assert_equal!(val.to_string(), "(a c)");
+    Ok(())
+}
+
+#[test]
+fn test_backquote_dotted_tail() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_string(r#"`(a . ,(list 1 2))"#)?;
+    assert_equal!(val.to_string(), "(a 1 2)");
+    Ok(())
+}
+
+#[test]
+fn test_nested_backquote_keeps_inner_unquote() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_program(
+        r#"
+        (setq b 2)
+        `(a `(c ,(d ,b)))
+        "#,
+    )?;
+    assert_equal!(val.to_string(), "(a (backquote (c (, (d 2)))))");
+    Ok(())
+}
+
+#[test]
+fn test_unquote_splicing_of_atom_is_an_error() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string(r#"`(a ,@1)"#).is_err(), true);
+    Ok(())
+}
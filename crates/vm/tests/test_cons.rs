@@ -0,0 +1,94 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_data_structures::{list, pair, Value};
+use minilisp_vm::{Result, VirtualMachine};
+
+#[test]
+fn test_cons_of_two_atoms() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_string(r#"(cons 1 2)"#)?;
+    assert_equal!(val.to_string(), "(1 . 2)");
+    let val = vm.eval_string(r#"(cons 1 '(2 3))"#)?;
+    assert_equal!(val.to_string(), "(1 2 3)");
+    Ok(())
+}
+
+#[test]
+fn test_car_and_cdr_of_dotted_pair() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string(r#"(car '(a . b))"#)?, Value::symbol("a"));
+    assert_equal!(vm.eval_string(r#"(cdr '(a . b))"#)?, Value::symbol("b"));
+    assert_equal!(vm.eval_string(r#"(cdr '(a b . c))"#)?.to_string(), "(b . c)");
+    Ok(())
+}
+
+#[test]
+fn test_list_predicates() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string(r#"(consp '(a . b))"#)?, Value::T);
+    assert_equal!(vm.eval_string(r#"(consp nil)"#)?, Value::Nil);
+    assert_equal!(vm.eval_string(r#"(listp nil)"#)?, Value::T);
+    assert_equal!(vm.eval_string(r#"(listp 1)"#)?, Value::Nil);
+    assert_equal!(vm.eval_string(r#"(proper-list-p '(a . b))"#)?, Value::Nil);
+    assert_equal!(
+        vm.eval_string(r#"(proper-list-p '(a b))"#)?,
+        Value::unsigned_integer(2u32)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_setcar_and_setcdr_are_shared() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_program("(setq a (list 1 2 3)) (setq b a) (setq c (cdr a))")?;
+    assert_equal!(vm.eval_string("(setcar b 'x)")?, Value::symbol("x"));
+    assert_equal!(vm.eval_string("a")?.to_string(), "(x 2 3)");
+    assert_equal!(vm.eval_string("(setcar c 'y)")?, Value::symbol("y"));
+    assert_equal!(vm.eval_string("a")?.to_string(), "(x y 3)");
+    vm.eval_string("(setcdr c 'z)")?;
+    assert_equal!(vm.eval_string("b")?.to_string(), "(x y . z)");
+    vm.eval_string("(setcdr a '(4 5))")?;
+    assert_equal!(vm.eval_string("b")?.to_string(), "(x 4 5)");
+    assert_equal!(vm.eval_string("(setcar nil 1)").is_err(), true);
+    assert_equal!(vm.eval_string("(setcdr 1 1)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_nconc() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_program("(setq a (list 1 2)) (setq b (list 3))")?;
+    assert_equal!(
+        vm.eval_string("(nconc nil a nil b 'tail)")?.to_string(),
+        "(1 2 3 . tail)"
+    );
+    assert_equal!(vm.eval_string("a")?.to_string(), "(1 2 3 . tail)");
+    assert_equal!(vm.eval_string("b")?.to_string(), "(3 . tail)");
+    assert_equal!(vm.eval_string("(nconc)")?, Value::Nil);
+    assert_equal!(vm.eval_string("(nconc nil 'x)")?, Value::symbol("x"));
+    assert_equal!(vm.eval_string("(nconc 1 a)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_nreverse() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_program("(setq a (list 1 2 3))")?;
+    assert_equal!(vm.eval_string("(nreverse a)")?.to_string(), "(3 2 1)");
+    assert_equal!(vm.eval_string("a")?.to_string(), "(1)");
+    assert_equal!(vm.eval_string("(nreverse nil)")?, Value::Nil);
+    assert_equal!(vm.eval_string("(nreverse '(1 . 2))").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_delq() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_program("(setq a '(x y x z x))")?;
+    assert_equal!(vm.eval_string("(delq 'x a)")?.to_string(), "(y z)");
+    assert_equal!(vm.eval_string("a")?.to_string(), "'(x y z)");
+    assert_equal!(vm.eval_string("(delq 'x '(x x))")?, Value::Nil);
+    assert_equal!(vm.eval_string("(delq 1 (list 1 2 1))")?.to_string(), "(2)");
+    assert_equal!(vm.eval_string("(delq 'x 'y)").is_err(), true);
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use k9::assert_equal;
+use minilisp_util::Traceback;
+use minilisp_vm::{Result, VirtualMachine};
+
+#[test]
+fn test_eq_compares_identity() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_program(
+        r#"
+        (setq a (list 1 2))
+        (setq s "string")
+        "#,
+    )?;
+    assert_equal!(vm.eval_string("(eq a a)")?.to_string(), "t");
+    assert_equal!(vm.eval_string("(eq a (list 1 2))")?.to_string(), "nil");
+    assert_equal!(vm.eval_string("(eq s s)")?.to_string(), "t");
+    assert_equal!(vm.eval_string(r#"(eq "string" "string")"#)?.to_string(), "nil");
+    assert_equal!(vm.eval_string("(eq 'foo 'foo)")?.to_string(), "t");
+    assert_equal!(vm.eval_string("(eq nil '())")?.to_string(), "t");
+    assert_equal!(vm.eval_string("(eq 1 1)")?.to_string(), "t");
+    Ok(())
+}
+
+#[test]
+fn test_eql_compares_numbers_of_the_same_type() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("(eql 1 1.0)")?.to_string(), "nil");
+    assert_equal!(vm.eval_string("(eql 1.5 1.5)")?.to_string(), "t");
+    assert_equal!(vm.eval_string("(eql 0.0 -0.0)")?.to_string(), "nil");
+    assert_equal!(vm.eval_string("(eql 3 (- 5 2))")?.to_string(), "t");
+    assert_equal!(vm.eval_string("(eql (/ 1 3) (/ 2 6))")?.to_string(), "t");
+    assert_equal!(
+        vm.eval_string("(eql (* 9223372036854775807 2) (* 9223372036854775807 2))")?
+            .to_string(),
+        "t"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_equal_compares_structure() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("(equal (list 1 2) '(1 2))")?.to_string(), "t");
+    assert_equal!(vm.eval_string("(equal (list 1 2) '(1 2.0))")?.to_string(), "nil");
+    assert_equal!(vm.eval_string(r#"(equal "string" "string")"#)?.to_string(), "t");
+    assert_equal!(
+        vm.eval_string("(equal (vconcat '(1 (a b))) (vconcat '(1 (a b))))")?.to_string(),
+        "t"
+    );
+    assert_equal!(
+        vm.eval_string("(equal (make-hash-table) (make-hash-table))")?.to_string(),
+        "nil"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_numeric_equality_across_types() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("(= 1 1.0)")?.to_string(), "t");
+    assert_equal!(vm.eval_string("(= 2 (- 5 3) 2.0)")?.to_string(), "t");
+    assert_equal!(vm.eval_string("(= (/ 1 2) 0.5)")?.to_string(), "t");
+    assert_equal!(vm.eval_string("(= (/ 1 3) 0.3333333333333333)")?.to_string(), "nil");
+    assert_equal!(vm.eval_string("(= 0.0e+NaN 0.0e+NaN)")?.to_string(), "nil");
+    assert_equal!(vm.eval_string("(/= 1 2)")?.to_string(), "t");
+    assert_equal!(vm.eval_string("(/= 1 1.0)")?.to_string(), "nil");
+    let error = vm.eval_string("(= 1 'a)").unwrap_err();
+    assert_equal!(
+        error.message().contains("wrong type argument: number-or-marker-p, 'a"),
+        true
+    );
+    Ok(())
+}
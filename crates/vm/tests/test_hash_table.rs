@@ -0,0 +1,70 @@
+use k9::assert_equal;
+use minilisp_util::Traceback;
+use minilisp_vm::{Result, VirtualMachine};
+
+#[test]
+fn test_puthash_gethash_and_remhash() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_program(
+        r#"
+        (setq table (make-hash-table :test 'equal))
+        (puthash "one" 1 table)
+        (puthash '(t w o) 2 table)
+        (puthash 'three 3 table)
+        "#,
+    )?;
+    assert_equal!(vm.eval_string(r#"(gethash "one" table)"#)?.to_string(), "1");
+    assert_equal!(vm.eval_string("(gethash '(t w o) table)")?.to_string(), "2");
+    assert_equal!(vm.eval_string("(gethash 'four table 0)")?.to_string(), "0");
+    assert_equal!(vm.eval_string("(gethash 'four table)")?.to_string(), "nil");
+    assert_equal!(vm.eval_string("(hash-table-count table)")?.to_string(), "3");
+    assert_equal!(
+        vm.eval_string("(hash-table-keys table)")?.to_string(),
+        r#"("one" (t w o) three)"#
+    );
+    vm.eval_string(r#"(remhash "one" table)"#)?;
+    assert_equal!(vm.eval_string("(hash-table-count table)")?.to_string(), "2");
+    assert_equal!(vm.eval_string(r#"(gethash "one" table)"#)?.to_string(), "nil");
+    assert_equal!(
+        vm.eval_string("(prin1-to-string table)")?.to_string(),
+        r##""#s(hash-table test equal data ((t w o) 2 three 3))""##
+    );
+    Ok(())
+}
+
+#[test]
+fn test_eql_tables_compare_strings_by_identity() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_program(r#"(setq table (make-hash-table)) (setq key "k") (puthash key 1 table)"#)?;
+    assert_equal!(vm.eval_string("(gethash key table)")?.to_string(), "1");
+    assert_equal!(vm.eval_string(r#"(gethash "k" table)"#)?.to_string(), "nil");
+    Ok(())
+}
+
+#[test]
+fn test_maphash() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_program(
+        r#"
+        (setq table (make-hash-table :test 'eq))
+        (setq copy (make-hash-table :test 'eq))
+        (puthash 'a 1 table)
+        (puthash 'b 2 table)
+        (defun copy-entry (key value) (puthash key value copy))
+        "#,
+    )?;
+    assert_equal!(vm.eval_string("(maphash 'copy-entry table)")?.to_string(), "nil");
+    assert_equal!(vm.eval_string("(hash-table-keys copy)")?.to_string(), "(a b)");
+    assert_equal!(vm.eval_string("(gethash 'b copy)")?.to_string(), "2");
+    Ok(())
+}
+
+#[test]
+fn test_hash_table_errors() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let error = vm.eval_string("(make-hash-table :test 'same)").unwrap_err();
+    assert_equal!(error.message().contains("invalid hash table test: same"), true);
+    let error = vm.eval_string("(gethash 'a 1)").unwrap_err();
+    assert_equal!(error.message().contains("wrong type argument: hash-table-p, 1"), true);
+    Ok(())
+}
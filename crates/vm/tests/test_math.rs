@@ -86,9 +86,100 @@ fn test_compound_arithmetic() -> Result<()> {
 #[test]
 fn test_eval_add_symbols() -> Result<()> {
     let mut vm = VirtualMachine::new();
-    vm.setq("a".into(), Value::unsigned_integer(1u64));
-    vm.setq("b".into(), Value::unsigned_integer(1u64));
+    vm.eval_string("(setq a 1)")?;
+    vm.eval_string("(setq b 1)")?;
     let val = vm.eval_string(r#"(+ a b)"#)?;
     assert_equal!(val, Value::unsigned_integer(2u64));
     Ok(())
 }
+
+#[test]
+fn test_arithmetic_across_numeric_types() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("(+ 1 -2)")?, Value::integer(-1i64));
+    assert_equal!(vm.eval_string("(+ 1 2.5)")?, Value::float(3.5));
+    assert_equal!(vm.eval_string("(* 2 -3 .5)")?, Value::float(-3.0));
+    assert_equal!(vm.eval_string("(- 2 5)")?, Value::integer(-3i64));
+    assert_equal!(vm.eval_string("(- 5)")?, Value::integer(-5i64));
+    assert_equal!(vm.eval_string("(+)")?, Value::unsigned_integer(0u32));
+    assert_equal!(vm.eval_string("(*)")?, Value::unsigned_integer(1u32));
+    Ok(())
+}
+
+#[test]
+fn test_integer_overflow_makes_big_integers() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(
+        vm.eval_string("(* 9223372036854775807 2)")?.to_string(),
+        "18446744073709551614"
+    );
+    assert_equal!(
+        vm.eval_string("(* 4294967296 4294967296 4294967296)")?.to_string(),
+        "79228162514264337593543950336"
+    );
+    assert_equal!(
+        vm.eval_string("(- (* 9223372036854775807 2) 9223372036854775807)")?,
+        Value::integer(i64::MAX)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_uneven_integer_division_makes_rationals() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("(/ 6 3)")?, Value::unsigned_integer(2u32));
+    assert_equal!(vm.eval_string("(/ 1 3)")?.to_string(), "1/3");
+    assert_equal!(vm.eval_string("(/ -4 6)")?.to_string(), "-2/3");
+    assert_equal!(vm.eval_string("(+ (/ 1 3) (/ 2 3))")?, Value::unsigned_integer(1u32));
+    assert_equal!(vm.eval_string("(* (/ 1 2) 3)")?.to_string(), "3/2");
+    assert_equal!(vm.eval_string("(+ (/ 1 2) .25)")?, Value::float(0.75));
+    assert_equal!(vm.eval_string("(/ 4)")?.to_string(), "1/4");
+    Ok(())
+}
+
+#[test]
+fn test_arithmetic_on_non_numbers_is_an_error() {
+    use minilisp_util::Traceback;
+    let mut vm = VirtualMachine::new();
+    let error = vm.eval_string(r#"(+ 1 "two")"#).unwrap_err();
+    assert_equal!(
+        error.message().contains("wrong type argument: number-or-marker-p, \"two\""),
+        true
+    );
+}
+
+#[test]
+fn test_division_by_zero_is_an_arith_error() {
+    use minilisp_util::Traceback;
+    let mut vm = VirtualMachine::new();
+    let error = vm.eval_string("(/ 1 0)").unwrap_err();
+    assert_equal!(
+        error.message().contains("arith-error: division by zero: (/ 1 0)"),
+        true
+    );
+    let error = vm.eval_string("(/ 10 2 0)").unwrap_err();
+    assert_equal!(
+        error.message().contains("arith-error: division by zero: (/ 5 0)"),
+        true
+    );
+    let error = vm.eval_string("(/ 1.5 0.0)").unwrap_err();
+    assert_equal!(error.message().contains("arith-error"), true);
+    let error = vm.eval_string("(/ 0)").unwrap_err();
+    assert_equal!(error.message().contains("arith-error"), true);
+}
+
+#[test]
+fn test_float_overflow_and_nan_are_errors() {
+    use minilisp_util::Traceback;
+    let mut vm = VirtualMachine::new();
+    let error = vm.eval_string("(* 1.0e308 10)").unwrap_err();
+    assert_equal!(
+        error.message().contains("overflow-error: result overflows: (* 1e308 10)"),
+        true
+    );
+    let error = vm.eval_string("(- 1.0e+INF 1.0e+INF)").unwrap_err();
+    assert_equal!(
+        error.message().contains("arith-error: result is not a number"),
+        true
+    );
+}
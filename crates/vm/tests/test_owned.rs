@@ -0,0 +1,101 @@
+use k9::assert_equal;
+use minilisp_data_structures::{car, cdr, list, Value};
+use minilisp_parser::parse_source;
+use minilisp_util::Traceback;
+use minilisp_vm::{Result, VirtualMachine};
+
+/// A host keeping a virtual machine and its results in a long-lived
+/// struct.
+struct Host {
+    vm: VirtualMachine<'static>,
+    results: Vec<Value<'static>>,
+}
+
+#[test]
+fn test_to_owned_outlives_the_source() {
+    let owned = {
+        let text = String::from(r#"(a "b" [c ?d] (e . 1.5) 'f)"#);
+        let value = parse_source(&text).expect("parse");
+        let owned = value.to_owned();
+        assert_equal!(owned.to_string(), value.to_string());
+        owned
+    };
+    assert_equal!(owned.to_string(), r#"(a "b" [c ?d] (e . 1.5) 'f)"#);
+    assert_equal!(owned.clone(), owned);
+}
+
+#[test]
+fn test_to_owned_copies_only_strings_borrowed_from_text() {
+    let text = String::from(r#""read""#);
+    let read = parse_source(&text).expect("parse");
+    assert_equal!(read.to_owned().is_eq(&read), false);
+    assert_equal!(read.to_owned().is_equal(&read), true);
+
+    let built = Value::string("built");
+    assert_equal!(built.to_owned().is_eq(&built), true);
+}
+
+#[test]
+fn test_eval_source_keeps_results_across_evaluations() -> Result<()> {
+    let mut host = Host {
+        vm: VirtualMachine::new(),
+        results: Vec::new(),
+    };
+    for line in ["(setq x (list 1 \"two\"))", "(cdr x)", "(car x)"] {
+        let line = line.to_string();
+        let value = host.vm.eval_source(&line)?;
+        host.results.push(value);
+    }
+    assert_equal!(
+        host.results,
+        vec![
+            list([Value::unsigned_integer(1u32), Value::from("two")]),
+            list([Value::from("two")]),
+            Value::unsigned_integer(1u32),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_eval_source_reports_the_failed_form() {
+    let mut vm = VirtualMachine::new();
+    let error = vm.eval_source("(setq a 1)\n(length a)").unwrap_err();
+    assert_equal!(error.message().contains("wrong type argument: sequencep, 1"), true);
+    assert_equal!(error.message().contains("--> <input>:2:1"), true);
+}
+
+#[test]
+fn test_to_owned_keeps_a_circular_cdr() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let list = vm.eval_program("(setq x (list 1 2 3)) (setcdr (cdr (cdr x)) x) x")?;
+    let owned = list.to_owned();
+    assert_equal!(owned.to_string(), list.to_string());
+    assert_equal!(owned.is_eq(&list), false);
+    assert_equal!(cdr(&cdr(&cdr(&owned))).is_eq(&owned), true);
+    Ok(())
+}
+
+#[test]
+fn test_to_owned_keeps_a_self_referential_car() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let list = vm.eval_program("(setq y (list 1 2)) (setcar y y) y")?;
+    let owned = list.to_owned();
+    assert_equal!(owned.to_string(), list.to_string());
+    assert_equal!(car(&owned).is_eq(&owned), true);
+    assert_equal!(car(&owned).is_eq(&list), false);
+    Ok(())
+}
+
+#[test]
+fn test_to_owned_keeps_shared_sublists_shared() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let list = vm.eval_program("(setq s (list 1)) (setq v (make-vector 1 s)) (list s s v v)")?;
+    let owned = list.to_owned();
+    assert_equal!(owned, list);
+    assert_equal!(car(&owned).is_eq(&car(&cdr(&owned))), true);
+    assert_equal!(car(&owned).is_eq(&car(&list)), false);
+    let vectors = cdr(&cdr(&owned));
+    assert_equal!(car(&vectors).is_eq(&car(&cdr(&vectors))), true);
+    Ok(())
+}
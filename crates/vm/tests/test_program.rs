@@ -0,0 +1,100 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_data_structures::{list, Value};
+use minilisp_vm::{Result, VirtualMachine};
+
+#[test]
+fn test_eval_program_returns_last_value() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_program(
+        r#"
+(setq a 1)
+(setq b 2)
+(list a b)
+"#,
+    )?;
+    assert_equal!(
+        val,
+        list([
+            Value::unsigned_integer(1u32),
+            Value::unsigned_integer(2u32),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_eval_program_with_defun() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_program(
+        r#"
+(defun sum (a b) (+ a b))
+(sum 40 2)
+"#,
+    )?;
+    assert_equal!(val, Value::unsigned_integer(42u32));
+    Ok(())
+}
+
+#[test]
+fn test_eval_empty_program() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_program("")?, Value::nil());
+    Ok(())
+}
+
+#[test]
+fn test_eval_program_skips_comments() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_program(
+        r#"
+;; set up
+(setq a 1) ; one
+#| (setq a 2) |#
+#;(setq a 3)
+(list a)
+"#,
+    )?;
+    assert_equal!(val, list([Value::unsigned_integer(1u32)]));
+    Ok(())
+}
+
+#[test]
+fn test_eval_program_of_bare_atoms() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_program("42")?, Value::unsigned_integer(42u32));
+    assert_equal!(vm.eval_program(r#""hello""#)?, Value::from("hello"));
+    assert_equal!(vm.eval_program("'foo")?, Value::quoted_symbol("foo"));
+    let val = vm.eval_program(
+        r#"
+(setq foo 3)
+foo
+"#,
+    )?;
+    assert_equal!(val, Value::unsigned_integer(3u32));
+    Ok(())
+}
+
+#[test]
+fn test_eval_quoted_datums() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("'42")?, Value::unsigned_integer(42u32));
+    assert_equal!(vm.eval_string(r#"'"s""#)?, Value::from("s"));
+    assert_equal!(vm.eval_string("'[1 b]")?.to_string(), "[1 b]");
+    assert_equal!(vm.eval_string("''a")?, Value::quoted_symbol("a"));
+    assert_equal!(vm.eval_string("(quote size)")?, Value::quoted_symbol("size"));
+    assert_equal!(vm.eval_string(r#"(list '1 '"two" 'c)"#)?.to_string(), r#"(1 "two" 'c)"#);
+    Ok(())
+}
+
+#[test]
+fn test_function_quote() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("#'car")?, Value::symbol("car"));
+    assert_equal!(vm.eval_string("(list #'car 1)")?.to_string(), "(car 1)");
+    assert_equal!(
+        vm.eval_string("#'(lambda (x) x)")?.to_string(),
+        "(lambda (x) x)"
+    );
+    Ok(())
+}
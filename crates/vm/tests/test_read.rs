@@ -0,0 +1,36 @@
+#![allow(unused)]
+use std::io::Cursor;
+
+use k9::assert_equal;
+use minilisp_data_structures::Value;
+use minilisp_vm::{Result, VirtualMachine};
+
+#[test]
+fn test_read_from_string() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string(r#"(read "(a \"b\" 1) c")"#)?.to_string(), "(a \"b\" 1)");
+    assert_equal!(vm.eval_string(r#"(read "[x ?y]")"#)?.to_string(), "[x ?y]");
+    assert_equal!(vm.eval_string(r#"(read "")"#).is_err(), true);
+    assert_equal!(vm.eval_string("(read 1)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_read_from_input() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.set_input(Cursor::new("(+ 1 2) foo\n\"bar\""));
+    assert_equal!(vm.eval_string("(read)")?.to_string(), "(+ 1 2)");
+    assert_equal!(vm.eval_string("(read)")?.to_string(), "foo");
+    assert_equal!(vm.eval_string("(read)")?.to_string(), "\"bar\"");
+    assert_equal!(vm.eval_string("(read)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_read_form_can_be_evaluated() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.set_input(Cursor::new("(+ 1 2)"));
+    let form = vm.read()?;
+    assert_equal!(vm.eval(form)?, Value::unsigned_integer(3u32));
+    Ok(())
+}
@@ -0,0 +1,60 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_data_structures::{list, Value};
+use minilisp_util::Traceback;
+use minilisp_vm::{Result, VirtualMachine};
+
+#[test]
+fn test_error_points_at_innermost_form() {
+    let mut vm = VirtualMachine::new();
+    let error = vm
+        .eval_program("(setq a 1)\n(list a\n  (length 1))")
+        .unwrap_err();
+    assert_equal!(error.message().contains("wrong type argument: sequencep, 1"), true);
+    assert_equal!(error.message().contains("--> <input>:3:3"), true);
+    assert_equal!(error.message().contains("3 | "), true);
+}
+
+#[test]
+fn test_error_reports_filename() {
+    let mut vm = VirtualMachine::new();
+    let error = vm
+        .eval_program_with_filename("\n(aref [a] 2)", "init.el")
+        .unwrap_err();
+    assert_equal!(error.message().contains("--> init.el:2:1"), true);
+}
+
+#[test]
+fn test_error_in_function_body_points_at_definition() {
+    let mut vm = VirtualMachine::new();
+    let error = vm
+        .eval_program(
+            r#"
+(defun broken (x)
+  (length x))
+(broken 1)
+"#,
+        )
+        .unwrap_err();
+    assert_equal!(error.message().contains("--> <input>:3:3"), true);
+}
+
+#[test]
+fn test_error_points_at_the_failed_one_of_equal_forms() {
+    let mut vm = VirtualMachine::new();
+    let error = vm
+        .eval_program("(setq x '(1))\n(list (length x) (setq x 1)\n      (length x))")
+        .unwrap_err();
+    assert_equal!(error.message().contains("--> <input>:3:7"), true);
+}
+
+#[test]
+fn test_spans_are_dropped_with_their_forms() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_program("(list 'a\n 'b)")?;
+    assert_equal!(vm.spans().is_empty(), true);
+
+    vm.eval_program("(defun f (x)\n  (length x))")?;
+    assert_equal!(vm.spans().is_empty(), false);
+    Ok(())
+}
@@ -22,3 +22,49 @@ fn test_print() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_char_to_string() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("(char-to-string ?a)")?, Value::from("a"));
+    assert_equal!(vm.eval_string(r"(char-to-string ?\n)")?, Value::from("\n"));
+    assert_equal!(vm.eval_string(r#"(char-to-string "a")"#).is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_string_to_char() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string(r#"(string-to-char "abc")"#)?, Value::char('a'));
+    assert_equal!(vm.eval_string(r#"(string-to-char "")"#)?, Value::char('\0'));
+    assert_equal!(vm.eval_string("(string-to-char ?a)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_prin1_to_string() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(
+        vm.eval_string(r#"(prin1-to-string '(a "b c" ?d))"#)?,
+        Value::from(r#"(a "b c" ?d)"#)
+    );
+    assert_equal!(vm.eval_string(r"(prin1-to-string 'a\ b)")?, Value::from(r"a\ b"));
+    assert_equal!(
+        vm.eval_string(r#"(read (prin1-to-string "x\ny"))"#)?,
+        Value::from("x\ny")
+    );
+    assert_equal!(vm.eval_string(r#"(princ "x")"#)?, Value::from("x"));
+    Ok(())
+}
+
+#[test]
+fn test_prin1_to_string_of_circular_lists() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_program("(setq a (list 1 2)) (setcdr (cdr a) a) (setq b (list a a))")?;
+    assert_equal!(vm.eval_string("(prin1-to-string a)")?, Value::from("(1 2 . #0)"));
+    assert_equal!(vm.eval_string("(prin1-to-string b)")?, Value::from("((1 2 . #0) (1 2 . #0))"));
+    vm.eval_string("(setq print-circle t)")?;
+    assert_equal!(vm.eval_string("(prin1-to-string a)")?, Value::from("#1=(1 2 . #1#)"));
+    assert_equal!(vm.eval_string("(prin1-to-string b)")?, Value::from("(#1=(1 2 . #1#) #1#)"));
+    Ok(())
+}
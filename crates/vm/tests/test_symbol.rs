@@ -0,0 +1,99 @@
+use k9::assert_equal;
+use minilisp_data_structures::{Symbol, Value};
+use minilisp_vm::{Result, VirtualMachine};
+
+#[test]
+fn test_intern() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string(r#"(intern "foo")"#)?, Value::symbol("foo"));
+    assert_equal!(vm.eval_string(r#"(intern "a b")"#)?, Value::symbol("a b"));
+    assert_equal!(vm.eval_string("(intern 'foo)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_intern_soft() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string(r#"(intern-soft "vm-never-interned")"#)?, Value::Nil);
+    vm.eval_string(r#"(intern "vm-interned")"#)?;
+    assert_equal!(
+        vm.eval_string(r#"(intern-soft "vm-interned")"#)?,
+        Value::symbol("vm-interned")
+    );
+    assert_equal!(vm.eval_string("(intern-soft 'car)")?, Value::symbol("car"));
+    assert_equal!(vm.eval_string("(intern-soft 1)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_symbol_name() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("(symbol-name 'foo)")?, Value::from("foo"));
+    assert_equal!(vm.eval_string(r#"(symbol-name (intern "x y"))"#)?, Value::from("x y"));
+    assert_equal!(vm.eval_string("(symbol-name nil)")?, Value::from("nil"));
+    assert_equal!(vm.eval_string(r#"(symbol-name "foo")"#).is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_put_get_and_symbol_plist() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("(symbol-plist 'fruit)")?, Value::Nil);
+    assert_equal!(vm.eval_string("(put 'fruit 'color 'red)")?, Value::symbol("red"));
+    vm.eval_string("(put 'fruit 'size 3)")?;
+    vm.eval_string("(put 'fruit 'color 'green)")?;
+    assert_equal!(vm.eval_string("(get 'fruit 'color)")?, Value::symbol("green"));
+    assert_equal!(vm.eval_string("(get 'fruit 'weight)")?, Value::Nil);
+    assert_equal!(
+        vm.eval_string("(symbol-plist 'fruit)")?.to_string(),
+        "(color green size 3)"
+    );
+    assert_equal!(vm.eval_string("(get 'vegetable 'color)")?, Value::Nil);
+    vm.eval_string("(put 'fruit :weight 120)")?;
+    assert_equal!(vm.eval_string("(get 'fruit :weight)")?.to_string(), "120");
+    Ok(())
+}
+
+#[test]
+fn test_property_lists_are_carried_by_the_symbol() -> Result<()> {
+    let mut first = VirtualMachine::new();
+    let mut second = VirtualMachine::new();
+    first.eval_string("(put 'carried-symbol 'owner 'first)")?;
+    assert_equal!(second.eval_string("(get 'carried-symbol 'owner)")?, Value::symbol("first"));
+    assert_equal!(
+        Symbol::new("carried-symbol").get(&Value::symbol("owner")),
+        Value::symbol("first")
+    );
+    Symbol::new("carried-symbol").put(&Value::symbol("size"), &Value::from(2.5));
+    assert_equal!(
+        first.eval_string("(symbol-plist 'carried-symbol)")?.to_string(),
+        "(owner first size 2.5)"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_setplist() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    vm.eval_string("(setplist 'planet '(moons 1 rings nil))")?;
+    assert_equal!(vm.eval_string("(get 'planet 'moons)")?.to_string(), "1");
+    vm.eval_string("(put 'planet 'rings t)")?;
+    assert_equal!(
+        vm.eval_string("(symbol-plist 'planet)")?.to_string(),
+        "(moons 1 rings t)"
+    );
+    assert_equal!(vm.eval_string("(put 1 'a 2)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_keywords_evaluate_to_themselves() -> Result<()> {
+    use minilisp_util::Traceback;
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string(":size")?, Value::symbol(":size"));
+    assert_equal!(vm.eval_string("(list :a 1)")?.to_string(), "(:a 1)");
+    let error = vm.eval_string("(setq :size 3)").unwrap_err();
+    assert_equal!(error.message().contains("setting-constant: :size"), true);
+    assert_equal!(vm.eval_string(":size")?, Value::symbol(":size"));
+    Ok(())
+}
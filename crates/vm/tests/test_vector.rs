@@ -0,0 +1,73 @@
+#![allow(unused)]
+use k9::assert_equal;
+use minilisp_data_structures::Value;
+use minilisp_vm::{Result, VirtualMachine};
+
+#[test]
+fn test_vector_evaluates_to_itself() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_string("[1 a (b c)]")?;
+    assert_equal!(val.to_string(), "[1 a (b c)]");
+    Ok(())
+}
+
+#[test]
+fn test_make_vector_and_length() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("(make-vector 3 'x)")?.to_string(), "[x x x]");
+    assert_equal!(vm.eval_string("(length [1 2 3])")?, Value::unsigned_integer(3u32));
+    assert_equal!(vm.eval_string("(length '(1 2))")?, Value::unsigned_integer(2u32));
+    assert_equal!(
+        vm.eval_string(r#"(length "héllo")"#)?,
+        Value::unsigned_integer(5u32)
+    );
+    assert_equal!(vm.eval_string("(length nil)")?, Value::unsigned_integer(0u32));
+    assert_equal!(vm.eval_string("(length 1)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_aref() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(vm.eval_string("(aref [a b c] 1)")?, Value::symbol("b"));
+    assert_equal!(vm.eval_string(r#"(aref "abc" 2)"#)?, Value::char('c'));
+    assert_equal!(vm.eval_string("(aref [a b c] 3)").is_err(), true);
+    assert_equal!(vm.eval_string("(aref [a b c] -1)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_aset_mutates_the_vector_in_place() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_program(
+        r#"
+(setq v (make-vector 2 0))
+(setq w v)
+(aset v 1 'b)
+w
+"#,
+    )?;
+    assert_equal!(val.to_string(), "[0 b]");
+    assert_equal!(vm.eval_string("(aset [a] 1 'b)").is_err(), true);
+    Ok(())
+}
+
+#[test]
+fn test_vconcat() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    let val = vm.eval_string(r#"(vconcat [1 2] '(a b) "xy" nil)"#)?;
+    assert_equal!(val.to_string(), "[1 2 a b ?x ?y]");
+    assert_equal!(vm.eval_string("(vconcat)")?, Value::vector([]));
+    Ok(())
+}
+
+#[test]
+fn test_vector_equality() -> Result<()> {
+    let mut vm = VirtualMachine::new();
+    assert_equal!(
+        vm.eval_string("[1 (a) \"b\"]")?,
+        vm.eval_string("(vconcat '(1 (a) \"b\"))")?
+    );
+    assert_equal!(vm.eval_string("[1 2]")? == vm.eval_string("[1 3]")?, false);
+    Ok(())
+}